@@ -0,0 +1,8 @@
+pub mod cbc;
+pub mod ctr;
+pub mod dh;
+pub mod ecb;
+pub mod mac;
+pub mod mt19937;
+pub mod srp;
+pub mod timing;
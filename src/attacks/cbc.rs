@@ -0,0 +1,391 @@
+use crate::base64::decode_b64;
+use crate::cipher::{decrypt, encrypt, gen_aes_128_key, strip_pkcs7_padding, CipherMode};
+use crate::Result;
+use rand::Rng;
+use std::error::Error;
+use std::fmt;
+
+pub(crate) const USERDATA_PREFIX: &[u8] = b"comment1=cooking%20MCs;userdata=";
+const USERDATA_SUFFIX: &[u8] = b";comment2=%20like%20a%20pound%20of%20bacon";
+pub(crate) const ADMIN_TOKEN: &[u8] = b";admin=true;";
+
+const PADDING_ORACLE_STRINGS: [&str; 10] = [
+    "MDAwMDAwTm93IHRoYXQgdGhlIHBhcnR5IGlzIGp1bXBpbmc=",
+    "MDAwMDAxV2l0aCB0aGUgYmFzcyBraWNrZWQgaW4gYW5kIHRoZSBWZWdhJ3MgYXJlIHB1bXBpbic=",
+    "MDAwMDAyUXVpY2sgdG8gdGhlIHBvaW50LCB0byB0aGUgcG9pbnQsIG5vIGZha2luZw==",
+    "MDAwMDAzQ29va2luZyBNQydzIGxpa2UgYSBwb3VuZCBvZiBiYWNvbg==",
+    "MDAwMDA0QnVybmluZyAnZW0sIGlmIHlvdSBhaW4ndCBxdWljayBhbmQgbmltYmxl",
+    "MDAwMDA1SSBnbyBjcmF6eSB3aGVuIEkgaGVhciBhIGN5bWJhbA==",
+    "MDAwMDA2QW5kIGEgaGlnaCBoYXQgd2l0aCBhIHNvdXBlZCB1cCB0ZW1wbw==",
+    "MDAwMDA3SSdtIG9uIGEgcm9sbCwgaXQncyB0aW1lIHRvIGdvIHNvbG8=",
+    "MDAwMDA4b2xsaW4nIGluIG15IGZpdmUgcG9pbnQgb2g=",
+    "MDAwMDA5aXRoIG15IHJhZy10b3AgZG93biBzbyBteSBoYWlyIGNhbiBibG93",
+];
+
+// Quotes out the metacharacters so userdata can't smuggle in a key of its own
+pub(crate) fn quote_userdata(input: &[u8]) -> Vec<u8> {
+    let mut quoted = Vec::with_capacity(input.len());
+    for &c in input {
+        match c {
+            b';' => quoted.extend(b"%3B"),
+            b'=' => quoted.extend(b"%3D"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted
+}
+
+pub(crate) fn wrap_userdata(input: &[u8]) -> Vec<u8> {
+    let mut start = USERDATA_PREFIX.to_vec();
+    start.extend(quote_userdata(input));
+    start.extend(USERDATA_SUFFIX);
+    start
+}
+
+pub(crate) fn contains_admin(plaintext: &[u8]) -> bool {
+    plaintext
+        .windows(ADMIN_TOKEN.len())
+        .any(|w| w == ADMIN_TOKEN)
+}
+
+// Wraps userdata in the challenge 16 cookie and CBC encrypts it. The key and IV are picked once and
+// reused for every call.
+pub struct UserdataOracle {
+    key: [u8; 16],
+    iv: [u8; 16],
+}
+
+impl UserdataOracle {
+    pub fn new() -> Self {
+        UserdataOracle {
+            key: gen_aes_128_key(),
+            iv: gen_aes_128_key(),
+        }
+    }
+
+    pub fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>> {
+        encrypt(
+            CipherMode::CBC,
+            &wrap_userdata(input),
+            &self.key,
+            Some(&self.iv),
+        )
+    }
+
+    pub fn is_admin(&self, input: &[u8]) -> Result<bool> {
+        let decrypted = decrypt(CipherMode::CBC, input, &self.key, Some(&self.iv))?;
+        Ok(contains_admin(&decrypted))
+    }
+}
+
+impl Default for UserdataOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// CBC bitflipping: a bit flipped in one ciphertext block flips the same bit in the next plaintext
+// block (and scrambles the block it was flipped in). We feed a sacrificial block followed by a
+// block of filler, then flip the sacrificial ciphertext so the filler decrypts to the admin token.
+pub fn cbc_bitflip_admin(oracle: impl Fn(&[u8]) -> Result<Vec<u8>>) -> Result<Vec<u8>> {
+    let block_size = 16;
+    let filler = vec![b'A'; ADMIN_TOKEN.len()];
+
+    let mut input = vec![b'A'; block_size];
+    input.extend(&filler);
+    let mut ciphertext = oracle(&input)?;
+
+    // The prefix is exactly two blocks, so our sacrificial block is the third
+    let sacrificial = USERDATA_PREFIX.len().div_ceil(block_size) * block_size;
+    for (i, (f, t)) in filler.iter().zip(ADMIN_TOKEN).enumerate() {
+        ciphertext[sacrificial + i] ^= f ^ t;
+    }
+
+    Ok(ciphertext)
+}
+
+// Anything that will tell us whether a CBC ciphertext decrypts to validly padded plaintext
+pub trait PaddingOracle {
+    fn check(&self, iv: &[u8], ct: &[u8]) -> bool;
+}
+
+impl<F: Fn(&[u8], &[u8]) -> bool> PaddingOracle for F {
+    fn check(&self, iv: &[u8], ct: &[u8]) -> bool {
+        self(iv, ct)
+    }
+}
+
+// The challenge 17 server: hands out one of a set of strings encrypted under a secret key, and will
+// happily tell anyone whether a ciphertext's padding checks out
+pub struct PaddingOracleServer {
+    key: [u8; 16],
+}
+
+impl PaddingOracleServer {
+    pub fn new() -> Self {
+        PaddingOracleServer {
+            key: gen_aes_128_key(),
+        }
+    }
+
+    // Returns the ciphertext along with the IV it was encrypted under
+    pub fn encrypt(&self) -> Result<(Vec<u8>, [u8; 16])> {
+        let i = rand::thread_rng().gen_range(0..PADDING_ORACLE_STRINGS.len());
+        self.encrypt_string(i)
+    }
+
+    fn encrypt_string(&self, i: usize) -> Result<(Vec<u8>, [u8; 16])> {
+        let iv = gen_aes_128_key();
+        let plaintext = decode_b64(PADDING_ORACLE_STRINGS[i]);
+        let ciphertext = encrypt(CipherMode::CBC, &plaintext, &self.key, Some(&iv))?;
+        Ok((ciphertext, iv))
+    }
+}
+
+impl Default for PaddingOracleServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PaddingOracle for PaddingOracleServer {
+    fn check(&self, iv: &[u8], ct: &[u8]) -> bool {
+        decrypt(CipherMode::CBC, ct, &self.key, Some(iv)).is_ok()
+    }
+}
+
+// CBC padding oracle attack. Each block is decrypted on its own by sending it with a forged IV: we
+// walk the forged IV byte by byte from the end until the oracle accepts the padding, which tells us
+// the block cipher output ("intermediate") at that position. XORing the intermediate with the real
+// previous block (or the real IV for the first block) gives the plaintext.
+pub fn padding_oracle_attack(
+    oracle: &impl PaddingOracle,
+    iv: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>> {
+    let block_size = iv.len();
+    if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(block_size) {
+        return Err("error: ciphertext is not a whole number of blocks".into());
+    }
+
+    let mut plaintext = Vec::with_capacity(ciphertext.len());
+    let mut previous = iv;
+    for block in ciphertext.chunks_exact(block_size) {
+        let intermediate = padding_oracle_block(oracle, previous, block)?;
+        plaintext.extend(intermediate.iter().zip(previous).map(|(i, p)| i ^ p));
+        previous = block;
+    }
+
+    Ok(strip_pkcs7_padding(&plaintext, block_size)?)
+}
+
+fn padding_oracle_block(
+    oracle: &impl PaddingOracle,
+    previous: &[u8],
+    block: &[u8],
+) -> Result<Vec<u8>> {
+    let block_size = block.len();
+    let mut intermediate = vec![0; block_size];
+    // Start from the real previous block so the bytes we haven't touched decrypt to the real
+    // plaintext
+    let mut forged = previous.to_vec();
+
+    for pad in 1..=block_size {
+        let pos = block_size - pad;
+        for (f, i) in forged[pos + 1..].iter_mut().zip(&intermediate[pos + 1..]) {
+            *f = i ^ pad as u8;
+        }
+
+        let mut found = None;
+        for guess in 0..=255u8 {
+            forged[pos] = guess;
+            if !oracle.check(&forged, block) {
+                continue;
+            }
+            // On the last byte a hit could be \x02\x02 (or longer) instead of \x01, so change the
+            // byte before it: real \x01 padding doesn't care
+            if pad == 1 && pos > 0 {
+                forged[pos - 1] ^= 0xff;
+                let still_valid = oracle.check(&forged, block);
+                forged[pos - 1] ^= 0xff;
+                if !still_valid {
+                    continue;
+                }
+            }
+            found = Some(guess);
+            break;
+        }
+
+        let guess = found.ok_or("error: no byte produced valid padding")?;
+        intermediate[pos] = guess ^ pad as u8;
+    }
+
+    Ok(intermediate)
+}
+
+// Returned by `KeyAsIvOracle::decrypt` when the plaintext has bytes outside of ASCII. It carries
+// the offending plaintext along with it, which is exactly what gives the key away.
+#[derive(Debug)]
+pub struct HighAsciiError {
+    pub plaintext: Vec<u8>,
+}
+
+impl fmt::Display for HighAsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error: invalid ASCII in plaintext {:?}", self.plaintext)
+    }
+}
+
+impl Error for HighAsciiError {}
+
+// The challenge 16 cookie under CBC, with the key doubling as the IV
+pub struct KeyAsIvOracle {
+    key: [u8; 16],
+}
+
+impl KeyAsIvOracle {
+    pub fn new() -> Self {
+        KeyAsIvOracle {
+            key: gen_aes_128_key(),
+        }
+    }
+
+    pub fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>> {
+        encrypt(
+            CipherMode::CBC,
+            &wrap_userdata(input),
+            &self.key,
+            Some(&self.key),
+        )
+    }
+
+    pub fn decrypt(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let decrypted = decrypt(CipherMode::CBC, input, &self.key, Some(&self.key))?;
+        if !decrypted.is_ascii() {
+            return Err(HighAsciiError {
+                plaintext: decrypted,
+            }
+            .into());
+        }
+        Ok(decrypted)
+    }
+}
+
+impl Default for KeyAsIvOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Recovers the key from a CBC oracle that uses it as the IV. Sending C1 || 0 || C1 decrypts the
+// first block as D(C1) ^ key and the third as D(C1) ^ 0, so XORing the two leaves the key. The
+// rest of the original ciphertext is kept on the end so the padding still checks out.
+pub fn recover_key_as_iv(
+    encrypt: impl Fn(&[u8]) -> Result<Vec<u8>>,
+    decrypt: impl Fn(&[u8]) -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    let block_size = 16;
+    let ciphertext = encrypt(&[b'A'; 16])?;
+    if ciphertext.len() < block_size * 5 {
+        return Err("error: need at least five blocks of ciphertext".into());
+    }
+
+    let first = &ciphertext[..block_size];
+    let mut modified = first.to_vec();
+    modified.extend(vec![0; block_size]);
+    modified.extend(first);
+    modified.extend(&ciphertext[block_size * 3..]);
+
+    let err = match decrypt(&modified) {
+        Ok(_) => return Err("error: modified plaintext was all ASCII".into()),
+        Err(err) => err,
+    };
+    let plaintext = &err
+        .downcast_ref::<HighAsciiError>()
+        .ok_or("error: decrypt failed for some other reason")?
+        .plaintext;
+
+    Ok(plaintext[..block_size]
+        .iter()
+        .zip(&plaintext[block_size * 2..block_size * 3])
+        .map(|(p1, p3)| p1 ^ p3)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn userdata_quotes_metacharacters() -> Result<()> {
+        let oracle = UserdataOracle::new();
+        assert!(!oracle.is_admin(&oracle.encrypt(b";admin=true;")?)?);
+        assert!(!oracle.is_admin(&oracle.encrypt(b"AAAA;admin=true;AAAA")?)?);
+        assert!(oracle.is_admin(&[0; 20]).is_err());
+        assert_eq!(
+            wrap_userdata(b";admin=true"),
+            b"comment1=cooking%20MCs;userdata=%3Badmin%3Dtrue;comment2=%20like%20a%20pound%20of%20bacon"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cbc_bitflip_makes_admin() -> Result<()> {
+        let oracle = UserdataOracle::new();
+        let forged = cbc_bitflip_admin(|input| oracle.encrypt(input))?;
+        assert!(oracle.is_admin(&forged)?);
+        Ok(())
+    }
+
+    #[test]
+    fn padding_oracle_every_string() -> Result<()> {
+        let server = PaddingOracleServer::new();
+        for (i, string) in PADDING_ORACLE_STRINGS.iter().enumerate() {
+            let (ciphertext, iv) = server.encrypt_string(i)?;
+            assert!(server.check(&iv, &ciphertext));
+            let recovered = padding_oracle_attack(&server, &iv, &ciphertext)?;
+            assert_eq!(recovered, decode_b64(string));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn padding_oracle_ambiguous_pad() -> Result<()> {
+        // The \x02 right before the last byte of each block makes \x02\x02 a valid padding too.
+        // Whether that or the real \x01 gets guessed first depends on the key, so try a few.
+        let plaintext = b"fourteen bytes\x02!and again.....\x02";
+        for _ in 0..32 {
+            let key = gen_aes_128_key();
+            let iv = gen_aes_128_key();
+            let ciphertext = encrypt(CipherMode::CBC, plaintext, &key, Some(&iv))?;
+
+            let oracle =
+                |iv: &[u8], ct: &[u8]| decrypt(CipherMode::CBC, ct, &key, Some(iv)).is_ok();
+            let recovered = padding_oracle_attack(&oracle, &iv, &ciphertext)?;
+            assert_eq!(recovered, plaintext);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn key_as_iv_rejects_high_ascii() -> Result<()> {
+        let oracle = KeyAsIvOracle::new();
+        assert!(oracle.decrypt(&oracle.encrypt(b"plain old ascii")?).is_ok());
+
+        let err = oracle
+            .decrypt(&oracle.encrypt(b"caf\xc3\xa9")?)
+            .unwrap_err();
+        let high = err.downcast_ref::<HighAsciiError>().unwrap();
+        assert_eq!(high.plaintext, wrap_userdata(b"caf\xc3\xa9"));
+        assert!(oracle.decrypt(&[0; 20]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn key_as_iv_recovers_key() -> Result<()> {
+        let oracle = KeyAsIvOracle::new();
+        let key = recover_key_as_iv(|input| oracle.encrypt(input), |input| oracle.decrypt(input))?;
+        assert_eq!(key, oracle.key);
+        Ok(())
+    }
+}
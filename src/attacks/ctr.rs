@@ -0,0 +1,188 @@
+use crate::attacks::cbc::{contains_admin, wrap_userdata, ADMIN_TOKEN, USERDATA_PREFIX};
+use crate::cipher::{
+    decrypt, encrypt, find_repeating_xor_key_with_size, find_single_byte_xor_key, gen_aes_128_key,
+    make_repeating_xor, CipherMode, CtrLayout,
+};
+use crate::Result;
+use rand::Rng;
+
+// Breaks a set of ciphertexts encrypted under CTR with the same key and nonce. They all share a
+// keystream, so truncating them to a common length and stacking them end to end is just
+// repeating-key XOR with a key as long as the shortest ciphertext. Returns that much keystream.
+pub fn break_fixed_nonce_ctr_truncated(ciphertexts: &[Vec<u8>]) -> Vec<u8> {
+    let len = match ciphertexts.iter().map(|c| c.len()).min() {
+        Some(len) if len > 0 => len,
+        _ => return vec![],
+    };
+    let concatenated: Vec<u8> = ciphertexts
+        .iter()
+        .flat_map(|c| &c[..len])
+        .copied()
+        .collect();
+
+    find_repeating_xor_key_with_size(&concatenated, len)
+}
+
+// Like `break_fixed_nonce_ctr_truncated`, but keeps going past the shortest ciphertext: each
+// keystream byte is solved from the column of every ciphertext long enough to reach it, which for
+// the common part is all of them. Returns every keystream byte with the fitting quotient of its
+// column (lower is better) and the number of ciphertexts that went into it, so the shaky columns
+// towards the end stand out.
+pub fn break_fixed_nonce_ctr(ciphertexts: &[Vec<u8>]) -> Vec<(u8, f64, usize)> {
+    let longest = ciphertexts.iter().map(|c| c.len()).max().unwrap_or(0);
+
+    (0..longest)
+        .map(|i| {
+            let column: Vec<u8> = ciphertexts
+                .iter()
+                .filter_map(|c| c.get(i))
+                .copied()
+                .collect();
+            let (key, _, score) = find_single_byte_xor_key(&column);
+            (key, score, column.len())
+        })
+        .collect()
+}
+
+// Random access read/write breaks CTR: editing the whole ciphertext to zeroes hands back the
+// keystream itself, and XORing that with the original ciphertext gives the plaintext.
+// `edit(ciphertext, offset, newtext)` returns the edited ciphertext.
+pub fn recover_ctr_plaintext_via_edit(
+    ciphertext: &[u8],
+    edit: impl Fn(&[u8], usize, &[u8]) -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    let keystream = edit(ciphertext, 0, &vec![0; ciphertext.len()])?;
+    Ok(make_repeating_xor(ciphertext, &keystream))
+}
+
+// The challenge 16 cookie again, this time under CTR with a key and nonce picked once
+pub struct CtrUserdataOracle {
+    key: [u8; 16],
+    nonce: [u8; 8],
+}
+
+impl CtrUserdataOracle {
+    pub fn new() -> Self {
+        CtrUserdataOracle {
+            key: gen_aes_128_key(),
+            nonce: rand::thread_rng().gen(),
+        }
+    }
+
+    pub fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let mode = CipherMode::CTR(CtrLayout::default());
+        encrypt(mode, &wrap_userdata(input), &self.key, Some(&self.nonce))
+    }
+
+    pub fn is_admin(&self, input: &[u8]) -> Result<bool> {
+        let mode = CipherMode::CTR(CtrLayout::default());
+        let decrypted = decrypt(mode, input, &self.key, Some(&self.nonce))?;
+        Ok(contains_admin(&decrypted))
+    }
+}
+
+impl Default for CtrUserdataOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// CTR bitflipping: every ciphertext bit maps straight onto the same plaintext bit, so XORing the
+// difference between our filler and the admin token in at the filler's offset rewrites just those
+// bytes. Unlike CBC there's no block to sacrifice.
+pub fn ctr_bitflip_admin(oracle: impl Fn(&[u8]) -> Result<Vec<u8>>) -> Result<Vec<u8>> {
+    let filler = vec![b'A'; ADMIN_TOKEN.len()];
+    let mut ciphertext = oracle(&filler)?;
+
+    let offset = USERDATA_PREFIX.len();
+    for (i, (f, t)) in filler.iter().zip(ADMIN_TOKEN).enumerate() {
+        ciphertext[offset + i] ^= f ^ t;
+    }
+
+    Ok(ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{decrypt, encrypt, gen_aes_128_key, CipherMode, CtrLayout};
+
+    fn encrypt_lines(text: &str) -> Result<(Vec<Vec<u8>>, Vec<u8>)> {
+        let key = gen_aes_128_key();
+        let mode = || CipherMode::CTR(CtrLayout::default());
+        let longest = text.lines().map(|l| l.len()).max().unwrap_or(0);
+        let keystream = encrypt(mode(), &vec![0; longest], &key, None)?;
+
+        let ciphertexts = text
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| encrypt(mode(), l.as_bytes(), &key, None))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((ciphertexts, keystream))
+    }
+
+    fn matching(found: &[u8], expected: &[u8]) -> usize {
+        found.iter().zip(expected).filter(|(f, e)| f == e).count()
+    }
+
+    // Frequency analysis won't get every column (the capitals starting each line are a weak spot),
+    // so these only expect the large majority of the keystream to come back
+    #[test]
+    fn fixed_nonce_ctr_truncated() -> Result<()> {
+        let text = std::fs::read_to_string("./inputs/s1c6_output.txt")?;
+        let (ciphertexts, keystream) = encrypt_lines(&text)?;
+
+        let found = break_fixed_nonce_ctr_truncated(&ciphertexts);
+        let shortest = ciphertexts.iter().map(|c| c.len()).min().unwrap();
+        assert_eq!(found.len(), shortest);
+        assert!(matching(&found, &keystream) * 10 >= shortest * 8);
+        Ok(())
+    }
+
+    #[test]
+    fn fixed_nonce_ctr_extended() -> Result<()> {
+        let text = std::fs::read_to_string("./inputs/s1c6_output.txt")?;
+        let (ciphertexts, keystream) = encrypt_lines(&text)?;
+
+        let found = break_fixed_nonce_ctr(&ciphertexts);
+        let longest = ciphertexts.iter().map(|c| c.len()).max().unwrap();
+        assert_eq!(found.len(), longest);
+
+        // Columns backed by a reasonable number of ciphertexts should mostly be right
+        let bytes: Vec<u8> = found.iter().map(|&(k, _, _)| k).collect();
+        let solid = found.iter().take_while(|&&(_, _, n)| n >= 10).count();
+        assert!(solid > 40);
+        assert!(matching(&bytes[..solid], &keystream) * 10 >= solid * 9);
+
+        // Over the part every ciphertext covers, it's the truncated break
+        let truncated = break_fixed_nonce_ctr_truncated(&ciphertexts);
+        assert_eq!(bytes[..truncated.len()], truncated[..]);
+
+        // The samples only ever shrink going right
+        assert!(found.windows(2).all(|w| w[0].2 >= w[1].2));
+        Ok(())
+    }
+
+    #[test]
+    fn ctr_userdata_quotes_metacharacters() -> Result<()> {
+        let oracle = CtrUserdataOracle::new();
+        assert!(!oracle.is_admin(&oracle.encrypt(b";admin=true;")?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn ctr_bitflip_makes_admin() -> Result<()> {
+        let oracle = CtrUserdataOracle::new();
+        let forged = ctr_bitflip_admin(|input| oracle.encrypt(input))?;
+        assert!(oracle.is_admin(&forged)?);
+
+        // Only the injected bytes changed, nothing around them got scrambled
+        let mode = CipherMode::CTR(CtrLayout::default());
+        let decrypted = decrypt(mode, &forged, &oracle.key, Some(&oracle.nonce))?;
+        let mut expected = wrap_userdata(&[b'A'; 12]);
+        let offset = USERDATA_PREFIX.len();
+        expected[offset..offset + ADMIN_TOKEN.len()].copy_from_slice(ADMIN_TOKEN);
+        assert_eq!(decrypted, expected);
+        Ok(())
+    }
+}
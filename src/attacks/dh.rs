@@ -0,0 +1,209 @@
+use crate::bignum::BigUint;
+use crate::dh::{decrypt_message, derive_aes_key};
+use crate::protocol::dh_echo::EchoMessage;
+use crate::protocol::dh_negotiated::NegotiatedMessage;
+use crate::protocol::{Direction, Middle};
+
+// Key fixing against the echo protocol: both public keys get swapped for p on the way through.
+// Each side then computes p^x mod p, which is 0 whatever its private key, so the middle knows the
+// shared secret without doing any work. `recovered` holds every message it relayed, decrypted.
+#[derive(Default)]
+pub struct KeyFixingMitm {
+    p: Option<BigUint>,
+    pub recovered: Vec<(Direction, Vec<u8>)>,
+}
+
+impl KeyFixingMitm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Middle<EchoMessage> for KeyFixingMitm {
+    fn intercept(&mut self, direction: Direction, message: EchoMessage) -> Option<EchoMessage> {
+        match message {
+            EchoMessage::Hello { p, g, .. } => {
+                self.p = Some(p.clone());
+                Some(EchoMessage::Hello {
+                    public: p.clone(),
+                    p,
+                    g,
+                })
+            }
+            EchoMessage::Reply { public } => Some(EchoMessage::Reply {
+                public: self.p.clone().unwrap_or(public),
+            }),
+            EchoMessage::Data(data) => {
+                let key = derive_aes_key(&BigUint::zero());
+                if let Ok(plaintext) = decrypt_message(&key, &data) {
+                    self.recovered.push((direction, plaintext));
+                }
+                Some(EchoMessage::Data(data))
+            }
+        }
+    }
+}
+
+// The generator a `MaliciousGroupMitm` slips into the negotiation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaliciousGenerator {
+    One,
+    P,
+    PMinusOne,
+}
+
+// A middle for the negotiated protocol that swaps g for a degenerate value when the group is
+// proposed. B acknowledges the group it was given and A goes along with it, so both sides run the
+// exchange with a generator whose powers this can predict. Keeps the public keys that go past to
+// settle the p - 1 case, and `recovered` holds every message it managed to decrypt.
+pub struct MaliciousGroupMitm {
+    generator: MaliciousGenerator,
+    p: Option<BigUint>,
+    publics: Vec<BigUint>,
+    pub recovered: Vec<(Direction, Vec<u8>)>,
+}
+
+impl MaliciousGroupMitm {
+    pub fn new(generator: MaliciousGenerator) -> Self {
+        MaliciousGroupMitm {
+            generator,
+            p: None,
+            publics: vec![],
+            recovered: vec![],
+        }
+    }
+
+    // What the shared secret has to be, once the group has gone past:
+    //   g = 1: every power of 1 is 1
+    //   g = p: every power of p is 0 mod p
+    //   g = p - 1: (-1)^ab, so p - 1 when both private keys are odd and 1 otherwise. Each public
+    //     key is (-1)^x itself, so they give away which of the two it is.
+    pub fn predicted_secret(&self) -> Option<BigUint> {
+        let p = self.p.as_ref()?;
+        let secret = match self.generator {
+            MaliciousGenerator::One => BigUint::one(),
+            MaliciousGenerator::P => BigUint::zero(),
+            MaliciousGenerator::PMinusOne => {
+                let minus_one = p - BigUint::one();
+                if self.publics.len() == 2 && self.publics.iter().all(|k| k == &minus_one) {
+                    minus_one
+                } else {
+                    BigUint::one()
+                }
+            }
+        };
+        Some(secret)
+    }
+}
+
+impl Middle<NegotiatedMessage> for MaliciousGroupMitm {
+    fn intercept(
+        &mut self,
+        direction: Direction,
+        message: NegotiatedMessage,
+    ) -> Option<NegotiatedMessage> {
+        match message {
+            NegotiatedMessage::Params { p, .. } => {
+                let g = match self.generator {
+                    MaliciousGenerator::One => BigUint::one(),
+                    MaliciousGenerator::P => p.clone(),
+                    MaliciousGenerator::PMinusOne => &p - BigUint::one(),
+                };
+                self.p = Some(p.clone());
+                Some(NegotiatedMessage::Params { p, g })
+            }
+            NegotiatedMessage::PublicKey(public) => {
+                self.publics.push(public.clone());
+                Some(NegotiatedMessage::PublicKey(public))
+            }
+            NegotiatedMessage::Data(data) => {
+                if let Some(secret) = self.predicted_secret() {
+                    if let Ok(plaintext) = decrypt_message(&derive_aes_key(&secret), &data) {
+                        self.recovered.push((direction, plaintext));
+                    }
+                }
+                Some(NegotiatedMessage::Data(data))
+            }
+            ack @ NegotiatedMessage::Ack { .. } => Some(ack),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh::Group;
+    use crate::protocol::dh_echo::{EchoBot, EchoClient};
+    use crate::protocol::dh_negotiated::{NegotiatingBot, NegotiatingClient};
+    use crate::protocol::run;
+    use crate::Result;
+
+    #[test]
+    fn key_fixing_reads_every_message() -> Result<()> {
+        let messages: Vec<Vec<u8>> = vec![
+            b"Attack at dawn".to_vec(),
+            b"No, wait, make it noon".to_vec(),
+            b"YELLOW SUBMARINE".to_vec(),
+        ];
+        let group = Group::modp_1536();
+        let client = EchoClient::new(group.clone(), messages.clone());
+        let mut mitm = KeyFixingMitm::new();
+        let session = run(client, EchoBot, &mut mitm)?;
+
+        // Neither side noticed anything
+        assert_eq!(session.a, messages);
+        assert_eq!(session.b, messages);
+
+        // Both saw p where the other's public key should have been
+        let publics: Vec<&BigUint> = session
+            .transcript
+            .iter()
+            .filter_map(|(_, m)| match m {
+                EchoMessage::Hello { public, .. } | EchoMessage::Reply { public } => Some(public),
+                EchoMessage::Data(_) => None,
+            })
+            .collect();
+        assert_eq!(publics, vec![&group.p, &group.p]);
+
+        // And every message was read going both ways
+        let expected: Vec<(Direction, Vec<u8>)> = messages
+            .iter()
+            .flat_map(|m| [(Direction::AToB, m.clone()), (Direction::BToA, m.clone())])
+            .collect();
+        assert_eq!(mitm.recovered, expected);
+        Ok(())
+    }
+
+    fn malicious_group(group: Group, generator: MaliciousGenerator) -> Result<MaliciousGroupMitm> {
+        let messages: Vec<Vec<u8>> = vec![b"Attack at dawn".to_vec(), b"Bring snacks".to_vec()];
+        let client = NegotiatingClient::new(group, messages.clone());
+        let mut mitm = MaliciousGroupMitm::new(generator);
+        let session = run(client, NegotiatingBot, &mut mitm)?;
+
+        // The exchange still works, so neither side has any reason to complain
+        assert_eq!(session.a, messages);
+        assert_eq!(session.b, messages);
+
+        let expected: Vec<(Direction, Vec<u8>)> = messages
+            .iter()
+            .flat_map(|m| [(Direction::AToB, m.clone()), (Direction::BToA, m.clone())])
+            .collect();
+        assert_eq!(mitm.recovered, expected);
+        Ok(mitm)
+    }
+
+    #[test]
+    fn generator_p_minus_one() -> Result<()> {
+        // Over a small group, so both outcomes come up quickly: 1 unless both private keys are
+        // odd, then p - 1
+        let group = Group::new(BigUint::from(37), BigUint::from(5));
+        let mut outcomes = vec![];
+        for _ in 0..40 {
+            let mitm = malicious_group(group.clone(), MaliciousGenerator::PMinusOne)?;
+            outcomes.push(mitm.predicted_secret().unwrap());
+        }
+        assert!(outcomes.contains(&BigUint::one()));
+        assert!(outcomes.contains(&BigUint::from(36)));
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+use crate::cipher::add_pkcs7_padding;
+use crate::Result;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::str;
+
+// Feeds the oracle a growing run of bytes until the ciphertext grows. The size of the jump is the
+// block size, and with PKCS#7 the number of bytes it took to get there tells us how much of the
+// last block the oracle's own data was already using.
+fn find_block_size(oracle: &impl Fn(&[u8]) -> Result<Vec<u8>>) -> Result<(usize, usize)> {
+    let initial = oracle(b"")?.len();
+    for i in 1..=256 {
+        let len = oracle(&b"A".repeat(i))?.len();
+        if len > initial {
+            let suffix_len = initial
+                .checked_sub(i)
+                .ok_or("error: oracle output is not padded")?;
+            return Ok((len - initial, suffix_len));
+        }
+    }
+    Err("error: ciphertext never grew, unable to find block size".into())
+}
+
+// Same trick as challenge 11: identical plaintext blocks give identical ciphertext blocks
+pub fn is_ecb(ciphertext: &[u8], block_size: usize) -> bool {
+    let mut set = HashSet::new();
+    ciphertext
+        .chunks_exact(block_size)
+        .any(|block| !set.insert(block))
+}
+
+// Byte-at-a-time ECB decryption against an oracle of the form
+// AES-128-ECB(your-string || unknown-string, key). Returns the recovered unknown-string along with
+// the number of times the oracle was queried.
+pub fn byte_at_a_time_ecb(oracle: impl Fn(&[u8]) -> Result<Vec<u8>>) -> Result<(Vec<u8>, usize)> {
+    let queries = Cell::new(0);
+    let oracle = |input: &[u8]| {
+        queries.set(queries.get() + 1);
+        oracle(input)
+    };
+
+    let (block_size, suffix_len) = find_block_size(&oracle)?;
+    if !is_ecb(&oracle(&b"A".repeat(block_size * 3))?, block_size) {
+        return Err("error: oracle is not using ECB".into());
+    }
+
+    let suffix = recover_suffix(&oracle, block_size, 0, suffix_len)?;
+    Ok((suffix, queries.get()))
+}
+
+// Byte-at-a-time ECB decryption against an oracle of the form
+// AES-128-ECB(random-prefix || your-string || unknown-string, key), where the prefix is fixed.
+// Returns the recovered unknown-string along with the number of times the oracle was queried.
+pub fn byte_at_a_time_ecb_prefix(
+    oracle: impl Fn(&[u8]) -> Result<Vec<u8>>,
+) -> Result<(Vec<u8>, usize)> {
+    let queries = Cell::new(0);
+    let oracle = |input: &[u8]| {
+        queries.set(queries.get() + 1);
+        oracle(input)
+    };
+
+    let (block_size, total_len) = find_block_size(&oracle)?;
+    if !is_ecb(&oracle(&b"A".repeat(block_size * 3))?, block_size) {
+        return Err("error: oracle is not using ECB".into());
+    }
+
+    // A prefix ending in our filler byte lines the blocks up too early and undercounts, and a
+    // suffix starting with it lines them up one filler early and overcounts. Between them they can
+    // fool at most two fillers, one low and one high, so of three the middle guess is right.
+    let mut guesses = [b'A', b'B', b'C']
+        .into_iter()
+        .map(|filler| find_prefix_len(&oracle, block_size, filler))
+        .collect::<Result<Vec<_>>>()?;
+    guesses.sort();
+    let prefix_len = guesses[1];
+    let suffix_len = total_len
+        .checked_sub(prefix_len)
+        .ok_or("error: prefix longer than oracle output")?;
+
+    let suffix = recover_suffix(&oracle, block_size, prefix_len, suffix_len)?;
+    Ok((suffix, queries.get()))
+}
+
+// Grows the run of filler bytes in front of two identical blocks until those blocks line up on a
+// block boundary and encrypt to the same ciphertext. The block they land on, minus the extra
+// filler needed to get there, gives the prefix length.
+fn find_prefix_len(
+    oracle: &impl Fn(&[u8]) -> Result<Vec<u8>>,
+    block_size: usize,
+    filler: u8,
+) -> Result<usize> {
+    for pad in 0..block_size {
+        let input = vec![filler; pad + block_size * 2];
+        let ciphertext = oracle(&input)?;
+        let blocks: Vec<&[u8]> = ciphertext.chunks_exact(block_size).collect();
+
+        if let Some(i) = blocks.windows(2).position(|w| w[0] == w[1]) {
+            return Ok(i * block_size - pad);
+        }
+    }
+    Err("error: unable to line up attacker blocks".into())
+}
+
+// ECB cut-and-paste: builds a ciphertext that decrypts to a profile with `role=admin` using only
+// ciphertexts produced by `profile_for`. Assumes 16 byte blocks and the
+// `email=...&uid=10&role=user` layout.
+pub fn cut_and_paste_admin(oracle: impl Fn(&str) -> Result<Vec<u8>>) -> Result<Vec<u8>> {
+    let block_size = 16;
+
+    // "email=" fills the first 6 bytes, so 10 more push "admin" and its padding onto a block of
+    // its own
+    let mut email = "A".repeat(block_size - "email=".len());
+    email.push_str(str::from_utf8(&add_pkcs7_padding(b"admin", block_size)?)?);
+    let admin = oracle(&email)?;
+    let admin = &admin[block_size..block_size * 2];
+
+    // Pick an email long enough that "user" is the only thing in the last block
+    let len = block_size * 2 - "email=".len() - "&uid=10&role=".len();
+    let mut email = "foo@bar.com".to_owned();
+    email.insert_str(0, &"f".repeat(len - email.len()));
+    let ciphertext = oracle(&email)?;
+
+    let mut forged = ciphertext[..block_size * 2].to_vec();
+    forged.extend(admin);
+    Ok(forged)
+}
+
+// Recovers `suffix_len` bytes that the oracle appends to our input. Any `prefix_len` bytes the
+// oracle puts in front of our input are padded out to a block boundary and skipped over.
+pub(crate) fn recover_suffix(
+    oracle: &impl Fn(&[u8]) -> Result<Vec<u8>>,
+    block_size: usize,
+    prefix_len: usize,
+    suffix_len: usize,
+) -> Result<Vec<u8>> {
+    let skip = prefix_len.div_ceil(block_size) * block_size;
+    let align = b"A".repeat(skip - prefix_len);
+    let mut recovered: Vec<u8> = Vec::with_capacity(suffix_len);
+
+    for i in 0..suffix_len {
+        // Line the next unknown byte up as the last byte of a block
+        let fill = b"A".repeat(block_size - 1 - (i % block_size));
+        let block = skip + (i / block_size) * block_size;
+
+        let mut input = align.clone();
+        input.extend(&fill);
+        let target = oracle(&input)?;
+        let target = &target[block..block + block_size];
+
+        // The block_size - 1 bytes we already know that precede the target byte
+        let mut known = fill.clone();
+        known.extend(&recovered);
+        let known = &known[known.len() - (block_size - 1)..];
+
+        // One query holding a block for every possible last byte
+        let mut dictionary = align.clone();
+        for b in 0..=255u8 {
+            dictionary.extend(known);
+            dictionary.push(b);
+        }
+        let dictionary = oracle(&dictionary)?;
+
+        let found = dictionary[skip..]
+            .chunks_exact(block_size)
+            .take(256)
+            .position(|c| c == target)
+            .ok_or("error: no dictionary entry matched")?;
+        recovered.push(found as u8);
+    }
+
+    Ok(recovered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base64::decode_b64;
+    use crate::cipher::{
+        encrypt, gen_aes_128_key, suffix_oracle, CipherMode, PrefixOracle, UNKNOWN_STRING,
+    };
+    use crate::profile::{decrypt_profile, encrypt_profile};
+
+    #[test]
+    fn byte_at_a_time_suffix_oracle() -> Result<()> {
+        let key = gen_aes_128_key();
+        let (recovered, queries) = byte_at_a_time_ecb(|input| suffix_oracle(input, &key))?;
+        let expected = decode_b64(UNKNOWN_STRING);
+        assert_eq!(recovered, expected);
+        // 7 to find the block size, 1 to detect ECB, then 2 for every byte
+        assert_eq!(queries, 7 + 1 + 2 * expected.len());
+        Ok(())
+    }
+
+    #[test]
+    fn byte_at_a_time_aligned_suffix() -> Result<()> {
+        let key = gen_aes_128_key();
+        let secret = b"exactly thirty-two bytes long!!!";
+        let oracle = |input: &[u8]| {
+            let mut plain = input.to_vec();
+            plain.extend(secret);
+            encrypt(CipherMode::ECB, &plain, &key, None)
+        };
+        let (recovered, _) = byte_at_a_time_ecb(oracle)?;
+        assert_eq!(recovered, secret);
+        Ok(())
+    }
+
+    #[test]
+    fn byte_at_a_time_rejects_cbc() {
+        let key = gen_aes_128_key();
+        let iv = [0; 16];
+        let oracle = |input: &[u8]| {
+            let mut plain = input.to_vec();
+            plain.extend(b"some unknown string");
+            encrypt(CipherMode::CBC, &plain, &key, Some(&iv))
+        };
+        assert!(byte_at_a_time_ecb(oracle).is_err());
+    }
+
+    #[test]
+    fn byte_at_a_time_every_prefix_len() -> Result<()> {
+        let expected = decode_b64(UNKNOWN_STRING);
+        for len in 0..48 {
+            let oracle = PrefixOracle::with_prefix_len(len);
+            let (recovered, _) = byte_at_a_time_ecb_prefix(|input| oracle.encrypt(input))?;
+            assert_eq!(recovered, expected, "prefix length {}", len);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn byte_at_a_time_suffix_starts_with_filler() -> Result<()> {
+        let key = gen_aes_128_key();
+        for secret in [&b"AAAA secret suffix"[..], b"BBB secret", b"CC secret"] {
+            // Prefixes ending in each filler too, so both ways of being fooled come up together
+            for prefix in [
+                &b"\x01\x02\x03\x04\x05"[..],
+                b"xyzA",
+                b"0123456789abcdefB",
+                b"C",
+            ] {
+                let oracle = |input: &[u8]| {
+                    let mut plain = prefix.to_vec();
+                    plain.extend(input);
+                    plain.extend(secret);
+                    encrypt(CipherMode::ECB, &plain, &key, None)
+                };
+                let (recovered, _) = byte_at_a_time_ecb_prefix(oracle)?;
+                assert_eq!(recovered, secret, "prefix {:?}", prefix);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cut_and_paste_role_admin() -> Result<()> {
+        let key = gen_aes_128_key();
+        let forged = cut_and_paste_admin(|email| encrypt_profile(email, &key))?;
+        let profile = decrypt_profile(&forged, &key)?;
+        let role = profile.iter().find(|(k, _)| k == "role").map(|(_, v)| v);
+        assert_eq!(role, Some(&"admin".to_owned()));
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::hash::MerkleDamgard;
+use std::ops::RangeInclusive;
+
+// Length extension against H(key || message) for a Merkle-Damgard hash H. The MAC is the hash
+// state after key || message || padding, so we can pick up from there and hash `suffix` on top
+// without ever knowing the key. Only the key's length matters (it decides the padding), so every
+// length in `key_lens` is tried until `verify` accepts the forgery. Returns the forged message, glue
+// padding included, and its MAC.
+pub fn length_extension<H: MerkleDamgard>(
+    message: &[u8],
+    mac: &[u8],
+    suffix: &[u8],
+    key_lens: RangeInclusive<usize>,
+    verify: impl Fn(&[u8], &[u8]) -> bool,
+) -> Option<(Vec<u8>, Vec<u8>)> {
+    key_lens.into_iter().find_map(|key_len| {
+        let glue = H::md_padding((key_len + message.len()) as u64);
+        let mut forged = message.to_vec();
+        forged.extend(&glue);
+        forged.extend(suffix);
+
+        let mut h = H::from_digest(mac, (key_len + message.len() + glue.len()) as u64)?;
+        h.update(suffix);
+        let forged_mac = h.finalize();
+
+        if verify(&forged, &forged_mac) {
+            Some((forged, forged_mac))
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::md4::{md4_prefix_mac, verify_md4_prefix_mac, Md4};
+    use crate::hash::sha1::{self, sha1_prefix_mac, verify_sha1_prefix_mac, Sha1};
+    use rand::seq::SliceRandom;
+
+    const WORDS: [&str; 12] = [
+        "apple",
+        "banana",
+        "cherry",
+        "dragonfruit",
+        "elderberry",
+        "fig",
+        "grape",
+        "honeydew",
+        "kiwi",
+        "lemon",
+        "mango",
+        "nectarine",
+    ];
+
+    #[test]
+    fn sha1_length_extension_forges_admin() {
+        let key = WORDS.choose(&mut rand::thread_rng()).unwrap().as_bytes();
+        let message =
+            b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+        let mac = sha1_prefix_mac(key, message);
+
+        let verify = |m: &[u8], mac: &[u8]| verify_sha1_prefix_mac(key, m, mac);
+        let (forged, forged_mac) =
+            length_extension::<Sha1>(message, &mac, b";admin=true", 0..=32, verify).unwrap();
+
+        assert!(forged.starts_with(message));
+        assert!(forged.ends_with(b";admin=true"));
+        assert!(verify_sha1_prefix_mac(key, &forged, &forged_mac));
+    }
+
+    #[test]
+    fn sha1_length_extension_key_out_of_range() {
+        let key = b"a key longer than the guesses";
+        let message = b"comment1=cooking%20MCs;userdata=foo";
+        let mac = sha1_prefix_mac(key, message);
+
+        let verify = |m: &[u8], mac: &[u8]| verify_sha1_prefix_mac(key, m, mac);
+        assert!(length_extension::<Sha1>(message, &mac, b";admin=true", 0..=16, verify).is_none());
+    }
+
+    #[test]
+    fn md4_length_extension_forges_admin() {
+        let key = WORDS.choose(&mut rand::thread_rng()).unwrap().as_bytes();
+        let message =
+            b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+        let mac = md4_prefix_mac(key, message);
+
+        let verify = |m: &[u8], mac: &[u8]| verify_md4_prefix_mac(key, m, mac);
+        let (forged, forged_mac) =
+            length_extension::<Md4>(message, &mac, b";admin=true", 0..=32, verify).unwrap();
+
+        assert!(forged.starts_with(message));
+        assert!(forged.ends_with(b";admin=true"));
+        assert!(verify_md4_prefix_mac(key, &forged, &forged_mac));
+    }
+
+    #[test]
+    fn md4_glue_padding_is_little_endian() {
+        // SHA-1's glue has the right shape but the length the wrong way round, so it can't forge
+        let key = b"kiwi";
+        let message = b"comment1=cooking%20MCs;userdata=foo";
+        let mac = md4_prefix_mac(key, message);
+
+        let len = key.len() + message.len();
+        let glue = sha1::md_padding(len as u64);
+        let mut forged = message.to_vec();
+        forged.extend(&glue);
+        forged.extend(b";admin=true");
+
+        let mut h = Md4::from_digest(&mac, (len + glue.len()) as u64).unwrap();
+        h.update(b";admin=true");
+        assert!(!verify_md4_prefix_mac(key, &forged, &h.finalize()));
+    }
+}
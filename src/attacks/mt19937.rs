@@ -0,0 +1,114 @@
+use crate::rng::{mt19937_cipher, Clock, Mt19937};
+use crate::{hex_to_u8, u8_to_hex};
+use rand::{Rng, RngCore};
+use std::ops::RangeInclusive;
+
+// Challenge 22: wait a random while, seed MT19937 with the current time, wait some more and hand
+// back the first output. All the waiting goes through `clock`, so a fake one makes this instant.
+pub fn seed_with_time_and_wait(clock: &impl Clock, rng: &mut impl Rng) -> u32 {
+    clock.sleep(rng.gen_range(40..=1000));
+    let mut mt = Mt19937::new(clock.now() as u32);
+    clock.sleep(rng.gen_range(40..=1000));
+    mt.next_u32()
+}
+
+// Brute-forces a UNIX timestamp seed given the first output of a generator seeded somewhere in
+// `window`. The most recent timestamps are tried first.
+pub fn crack_timestamp_seed(output: u32, window: RangeInclusive<u64>) -> Option<u32> {
+    window
+        .rev()
+        .map(|t| t as u32)
+        .find(|&seed| Mt19937::new(seed).next_u32() == output)
+}
+
+// Encrypts a random number of random bytes followed by `known` under a random 16-bit seed
+pub fn mt19937_cipher_oracle(known: &[u8], seed: u16) -> Vec<u8> {
+    let mut rng = rand::thread_rng();
+    let mut plaintext: Vec<u8> = (0..rng.gen_range(5..=20)).map(|_| rng.gen()).collect();
+    plaintext.extend(known);
+    mt19937_cipher(&plaintext, seed)
+}
+
+// A 16-bit seed is only 65536 keys, so try them all and keep the one that decrypts the end of the
+// ciphertext to the plaintext we know is there
+pub fn crack_mt19937_cipher_seed(ciphertext: &[u8], known_suffix: &[u8]) -> Option<u16> {
+    (0..=u16::MAX).find(|&seed| mt19937_cipher(ciphertext, seed).ends_with(known_suffix))
+}
+
+// Password reset token made of 16 bytes of MT19937 output, seeded with the current time
+pub fn reset_token(clock: &impl Clock) -> String {
+    let mut token = [0; 16];
+    Mt19937::new(clock.now() as u32).fill_bytes(&mut token);
+    u8_to_hex(&token)
+}
+
+// Whether a token could have come from `reset_token` at some point in the last `secs` seconds
+pub fn is_time_seeded_token(token: &str, clock: &impl Clock, secs: u64) -> bool {
+    if token.is_empty() || !token.is_ascii() || !token.len().is_multiple_of(2) {
+        return false;
+    }
+    let token = match hex_to_u8(token) {
+        Ok(token) => token,
+        Err(_) => return false,
+    };
+    let now = clock.now();
+
+    (now.saturating_sub(secs)..=now).any(|t| {
+        let mut guess = vec![0; token.len()];
+        Mt19937::new(t as u32).fill_bytes(&mut guess);
+        guess == token
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::FakeClock;
+
+    #[test]
+    fn crack_seed_from_time() {
+        let start = 1_700_000_000;
+        let clock = FakeClock::new(start);
+        let output = seed_with_time_and_wait(&clock, &mut rand::thread_rng());
+
+        let seed = crack_timestamp_seed(output, start..=clock.now()).unwrap();
+        assert!((start + 40..=clock.now() - 40).contains(&(seed as u64)));
+        assert_eq!(Mt19937::new(seed).next_u32(), output);
+    }
+
+    #[test]
+    fn crack_seed_outside_window() {
+        let clock = FakeClock::new(1_700_000_000);
+        let output = seed_with_time_and_wait(&clock, &mut rand::thread_rng());
+
+        let end = clock.now();
+        assert_eq!(crack_timestamp_seed(output, end - 30..=end), None);
+    }
+
+    #[test]
+    fn crack_mt19937_cipher() {
+        let seed: u16 = rand::random();
+        let ciphertext = mt19937_cipher_oracle(&b"A".repeat(14), seed);
+        assert_eq!(
+            crack_mt19937_cipher_seed(&ciphertext, &b"A".repeat(14)),
+            Some(seed)
+        );
+    }
+
+    #[test]
+    fn detect_time_seeded_token() {
+        let clock = FakeClock::new(1_700_000_000);
+        let token = reset_token(&clock);
+        clock.sleep(120);
+        assert!(is_time_seeded_token(&token, &clock, 300));
+        assert!(!is_time_seeded_token(&token, &clock, 60));
+
+        let mut random = [0; 16];
+        rand::thread_rng().fill(&mut random);
+        assert!(!is_time_seeded_token(&u8_to_hex(&random), &clock, 300));
+        assert!(!is_time_seeded_token("not hex", &clock, 300));
+        assert!(!is_time_seeded_token(&token[1..], &clock, 300));
+        assert!(!is_time_seeded_token("", &clock, 300));
+        assert!(!is_time_seeded_token("aéb", &clock, 300));
+    }
+}
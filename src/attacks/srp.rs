@@ -0,0 +1,19 @@
+use crate::bignum::BigUint;
+use crate::srp::{session_key, Handshake, Params};
+use crate::Result;
+use std::net::ToSocketAddrs;
+
+// Logs in as `email` without the password by sending A = multiple * N. The server computes
+// S = (A * v^u)^b mod N, and with A a multiple of N that's 0 whatever v and b are, so K = H(0) and
+// the proof can be made without knowing anything. Works for any multiple, 0 included, against a
+// server that skips the RFC's check that A isn't 0 mod N.
+pub fn zero_key_login(
+    addr: impl ToSocketAddrs,
+    params: &Params,
+    email: &str,
+    multiple: u64,
+) -> Result<bool> {
+    let public = &params.group.p * BigUint::from(multiple);
+    let handshake = Handshake::start(addr, email, &public)?;
+    handshake.finish(&session_key(&BigUint::zero()))
+}
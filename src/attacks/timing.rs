@@ -0,0 +1,215 @@
+use crate::{u8_to_hex, Result};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How many requests are in flight at once. Each one blocks for as long as the server sleeps, so
+// running them side by side is what keeps the attack from taking hours. Kept well under the
+// listen backlog so connections don't get dropped and retried, which would swamp the signal.
+const WORKERS: usize = 64;
+// How many candidates make it to the last round at each position
+const FINALISTS: usize = 16;
+
+// Sends one signature for `file` and returns whether the server accepted it and how long the round
+// trip took
+pub fn timed_request(
+    addr: SocketAddr,
+    file: &str,
+    signature: &[u8],
+) -> io::Result<(bool, Duration)> {
+    let start = Instant::now();
+    let mut stream = TcpStream::connect(addr)?;
+    // A single write, as split ones can sit in Nagle's buffer waiting on a delayed ACK
+    let request = format!(
+        "GET /test?file={}&signature={} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        file,
+        u8_to_hex(signature)
+    );
+    stream.write_all(request.as_bytes())?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    Ok((response.starts_with("HTTP/1.1 200"), start.elapsed()))
+}
+
+// Recovers the HMAC of `file` from a server that compares signatures byte by byte and sleeps after
+// every match, so a guess with one more correct byte takes noticeably longer. Each candidate is
+// scored by its fastest request rather than the mean or median: scheduling and network noise only
+// ever add time, so the minimum is the closest thing to what the server actually spent.
+pub fn recover_hmac_via_timing(
+    addr: SocketAddr,
+    file: &str,
+    mac_len: usize,
+    samples: usize,
+) -> Result<Vec<u8>> {
+    let mut known = vec![];
+    // How far the winner stood out from the runner-up at each known position
+    let mut margins: Vec<Duration> = vec![];
+    let mut first_guess = None;
+    let mut misses = 0;
+
+    for _ in 0..mac_len * 8 {
+        let (best, margin) = match next_byte(addr, file, &known, mac_len, samples)? {
+            Step::Accepted(c) => {
+                known.push(c);
+                return Ok(known);
+            }
+            Step::Slowest(c, margin) => (c, margin),
+        };
+
+        // A right byte should stand out about as much as the ones before it did. The first has
+        // nothing to go by, so it has to win twice in a row instead. The last byte never counts,
+        // since getting it right would have been accepted outright.
+        let mut sorted = margins.clone();
+        sorted.sort();
+        let stands_out = match sorted.get(sorted.len().saturating_sub(1) / 2) {
+            Some(&expected) => margin >= expected / 3,
+            None => first_guess.replace(best) == Some(best),
+        };
+        if stands_out && known.len() + 1 < mac_len {
+            known.push(best);
+            margins.push(margin);
+            misses = 0;
+            continue;
+        }
+
+        // Nothing stood out. Either that was noise and measuring again will do, or an earlier
+        // byte is wrong and nothing here can match, so a second miss steps back and redoes the
+        // previous byte rather than carrying on with garbage.
+        if !margins.is_empty() {
+            misses += 1;
+        }
+        if misses == 2 {
+            known.pop();
+            margins.pop();
+            first_guess = None;
+            misses = 0;
+        }
+    }
+
+    Err("error: no signature was accepted".into())
+}
+
+enum Step {
+    Accepted(u8),
+    // The slowest candidate and how far ahead of the runner-up it was
+    Slowest(u8, Duration),
+}
+
+// Times every candidate for the next position `samples` times, then the few slowest again with
+// four times as many samples, so the real contenders get a closer look than the hopeless ones
+fn next_byte(
+    addr: SocketAddr,
+    file: &str,
+    known: &[u8],
+    mac_len: usize,
+    samples: usize,
+) -> Result<Step> {
+    let candidates: Vec<u8> = (0..=255).collect();
+    let timings = probe(addr, file, known, mac_len, &candidates, samples)?;
+    if let Some(c) = accepted(&timings) {
+        return Ok(Step::Accepted(c));
+    }
+
+    let finalists: Vec<u8> = rank(&timings)
+        .iter()
+        .take(FINALISTS)
+        .map(|&(c, _)| c)
+        .collect();
+    let timings = probe(addr, file, known, mac_len, &finalists, samples * 4)?;
+    if let Some(c) = accepted(&timings) {
+        return Ok(Step::Accepted(c));
+    }
+    let ranked = rank(&timings);
+    Ok(Step::Slowest(ranked[0].0, ranked[0].1 - ranked[1].1))
+}
+
+// Times `samples` requests for each candidate at the next position, padding the rest of the
+// signature with zeroes. Returns (candidate, time, accepted) for every request.
+fn probe(
+    addr: SocketAddr,
+    file: &str,
+    known: &[u8],
+    mac_len: usize,
+    candidates: &[u8],
+    samples: usize,
+) -> Result<Vec<(u8, Duration, bool)>> {
+    let jobs: Vec<u8> = candidates
+        .iter()
+        .flat_map(|&c| std::iter::repeat_n(c, samples))
+        .collect();
+    let next = AtomicUsize::new(0);
+
+    let results: Vec<io::Result<Vec<_>>> = thread::scope(|s| {
+        let workers: Vec<_> = (0..WORKERS.min(jobs.len()))
+            .map(|_| {
+                s.spawn(|| -> io::Result<Vec<_>> {
+                    let mut timings = vec![];
+                    while let Some(&c) = jobs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let mut guess = known.to_vec();
+                        guess.push(c);
+                        guess.resize(mac_len, 0);
+                        let (ok, time) = timed_request(addr, file, &guess)?;
+                        timings.push((c, time, ok));
+                    }
+                    Ok(timings)
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|w| {
+                w.join()
+                    .unwrap_or_else(|_| Err(io::Error::other("worker panicked")))
+            })
+            .collect()
+    });
+
+    let mut timings = vec![];
+    for result in results {
+        timings.extend(result?);
+    }
+    Ok(timings)
+}
+
+fn accepted(timings: &[(u8, Duration, bool)]) -> Option<u8> {
+    timings.iter().find(|&&(_, _, ok)| ok).map(|&(c, _, _)| c)
+}
+
+// Each candidate with its fastest time, slowest first
+fn rank(timings: &[(u8, Duration, bool)]) -> Vec<(u8, Duration)> {
+    let mut by_candidate = [const { Vec::new() }; 256];
+    for &(c, time, _) in timings {
+        by_candidate[c as usize].push(time);
+    }
+
+    let mut ranked: Vec<(u8, Duration)> = by_candidate
+        .iter()
+        .enumerate()
+        .filter(|(_, times)| !times.is_empty())
+        .map(|(c, times)| (c as u8, *times.iter().min().unwrap()))
+        .collect();
+    ranked.sort_by_key(|&(_, time)| std::cmp::Reverse(time));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::gen_aes_128_key;
+    use crate::hash::hmac::Hmac;
+    use crate::server;
+    use openssl::sha::Sha1;
+
+    #[test]
+    fn timing_leak_recovers_hmac() -> Result<()> {
+        let key = gen_aes_128_key().to_vec();
+        let addr = server::spawn(server::hmac_handler(key.clone(), Duration::from_millis(20)))?;
+
+        let mac = recover_hmac_via_timing(addr, "foo", 20, 1)?;
+        assert_eq!(mac, Hmac::<Sha1>::mac(&key, b"foo"));
+        assert!(timed_request(addr, "foo", &mac)?.0);
+        Ok(())
+    }
+}
@@ -62,10 +62,9 @@ fn encode_table(alphabet: &str) -> [u8; 64] {
 }
 
 fn align_up(num: usize, to: usize) -> usize {
-    // By subtracting one, we get "as close as possible"
-    // then we do integer division to get the "multiple"
+    // Round up the integer division to get the "multiple"
     // and multiply the multiple by the target number
-    ((num + to - 1) / to) * to
+    num.div_ceil(to) * to
 }
 
 #[cfg(test)]
@@ -0,0 +1,690 @@
+use crate::{hex_to_u8, u8_to_hex, Result};
+use rand::Rng;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Shl, Shr, Sub};
+
+// Arbitrary precision unsigned integer. Stored as 32-bit limbs, least significant first, with no
+// zero limbs at the top so that every value has exactly one representation (zero is no limbs).
+// 32-bit limbs keep every intermediate product inside a u64.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint { limbs: vec![] }
+    }
+
+    pub fn one() -> Self {
+        BigUint { limbs: vec![1] }
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    pub fn from_bytes_be(bytes: &[u8]) -> Self {
+        let limbs = bytes
+            .rchunks(4)
+            .map(|chunk| chunk.iter().fold(0, |acc, &b| (acc << 8) | b as u32))
+            .collect();
+        Self::from_limbs(limbs)
+    }
+
+    // Minimal big-endian bytes, so zero is empty (the same as `openssl::bn::BigNum::to_vec`)
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let bytes: Vec<u8> = self
+            .limbs
+            .iter()
+            .rev()
+            .flat_map(|l| l.to_be_bytes())
+            .collect();
+        let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+        bytes[zeros..].to_vec()
+    }
+
+    // Accepts odd lengths, unlike `hex_to_u8`, since numbers are often written without a leading
+    // zero
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        if !hex.is_ascii() {
+            return Err("error: invalid hex string".into());
+        }
+        let bytes = if hex.len() % 2 == 1 {
+            hex_to_u8(&format!("0{}", hex))?
+        } else {
+            hex_to_u8(hex)?
+        };
+        Ok(Self::from_bytes_be(&bytes))
+    }
+
+    pub fn to_hex(&self) -> String {
+        if self.is_zero() {
+            return "0".to_owned();
+        }
+        u8_to_hex(&self.to_bytes_be())
+    }
+
+    // Uniformly random below `bound`, by drawing just as many bits as it has and trying again
+    // whenever that comes out too big. Fewer than half the draws get thrown away.
+    pub fn random_below(rng: &mut impl Rng, bound: &BigUint) -> BigUint {
+        assert!(!bound.is_zero(), "no numbers below zero");
+        let bits = bound.bits();
+        let mut bytes = vec![0; bits.div_ceil(8)];
+        loop {
+            rng.fill(&mut bytes[..]);
+            if !bits.is_multiple_of(8) {
+                bytes[0] &= (1 << (bits % 8)) - 1;
+            }
+            let n = Self::from_bytes_be(&bytes);
+            if &n < bound {
+                return n;
+            }
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|l| l & 1 == 1)
+    }
+
+    // Number of significant bits, zero for zero
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs
+            .get(i / 32)
+            .is_some_and(|l| (l >> (i % 32)) & 1 == 1)
+    }
+
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if self < other {
+            return None;
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = false;
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let b = other.limbs.get(i).copied().unwrap_or(0);
+            let (d, o1) = a.overflowing_sub(b);
+            let (d, o2) = d.overflowing_sub(borrow as u32);
+            limbs.push(d);
+            borrow = o1 || o2;
+        }
+        Some(Self::from_limbs(limbs))
+    }
+
+    // Quotient and remainder in one go. Panics on division by zero, like the primitive integers.
+    pub fn divrem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        assert!(!divisor.is_zero(), "attempt to divide by zero");
+        if self < divisor {
+            return (BigUint::zero(), self.clone());
+        }
+        if divisor.limbs.len() == 1 {
+            let (q, r) = divrem_limb(&self.limbs, divisor.limbs[0]);
+            return (Self::from_limbs(q), BigUint::from(r as u64));
+        }
+        let (q, r) = divrem_knuth(&self.limbs, &divisor.limbs);
+        (Self::from_limbs(q), Self::from_limbs(r))
+    }
+
+    // self^exp mod modulus by left-to-right square and multiply
+    pub fn modpow(&self, exp: &BigUint, modulus: &BigUint) -> BigUint {
+        assert!(
+            !modulus.is_zero(),
+            "attempt to calculate modpow with a zero modulus"
+        );
+        let base = self % modulus;
+        let mut result = BigUint::one() % modulus;
+        for i in (0..exp.bits()).rev() {
+            result = &(&result * &result) % modulus;
+            if exp.bit(i) {
+                result = &(&result * &base) % modulus;
+            }
+        }
+        result
+    }
+
+    pub fn gcd(&self, other: &BigUint) -> BigUint {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    // The x with self * x = 1 mod modulus, if there is one. Extended Euclid, keeping the
+    // coefficient reduced mod `modulus` so it never goes negative.
+    pub fn modinv(&self, modulus: &BigUint) -> Option<BigUint> {
+        if modulus.is_zero() {
+            return None;
+        }
+        let (mut old_r, mut r) = (self % modulus, modulus.clone());
+        let (mut old_s, mut s) = (BigUint::one() % modulus, BigUint::zero());
+        while !r.is_zero() {
+            let (q, rem) = old_r.divrem(&r);
+            old_r = std::mem::replace(&mut r, rem);
+            // old_s - q * s, mod modulus
+            let qs = &(&q * &s) % modulus;
+            let next = &(&old_s + modulus - qs) % modulus;
+            old_s = std::mem::replace(&mut s, next);
+        }
+        (old_r == BigUint::one()).then_some(old_s)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        Self::from_limbs(vec![n as u32, (n >> 32) as u32])
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Decimal, by peeling off nine digits at a time
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad_integral(true, "", "0");
+        }
+        let mut chunks = vec![];
+        let mut limbs = self.limbs.clone();
+        while !limbs.is_empty() {
+            let (q, r) = divrem_limb(&limbs, 1_000_000_000);
+            chunks.push(r);
+            limbs = BigUint::from_limbs(q).limbs;
+        }
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = self.to_hex();
+        let digits = hex.trim_start_matches('0');
+        f.pad_integral(true, "0x", if digits.is_empty() { "0" } else { digits })
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (&self.limbs, &other.limbs)
+        } else {
+            (&other.limbs, &self.limbs)
+        };
+        let mut limbs = Vec::with_capacity(long.len() + 1);
+        let mut carry = 0;
+        for (i, &a) in long.iter().enumerate() {
+            let sum = a as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+// Panics if the result would be negative, like the primitive integers. `checked_sub` doesn't.
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+// Schoolbook multiplication
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let t = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Div<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.divrem(other).0
+    }
+}
+
+impl Rem<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.divrem(other).1
+    }
+}
+
+impl Shl<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, bits: usize) -> BigUint {
+        if self.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0; bits / 32];
+        limbs.extend(shl_limbs(&self.limbs, (bits % 32) as u32));
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Shr<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: usize) -> BigUint {
+        let skip = bits / 32;
+        if skip >= self.limbs.len() {
+            return BigUint::zero();
+        }
+        let shift = (bits % 32) as u32;
+        let limbs = &self.limbs[skip..];
+        if shift == 0 {
+            return BigUint::from_limbs(limbs.to_vec());
+        }
+        let shifted = (0..limbs.len())
+            .map(|i| {
+                let high = limbs.get(i + 1).copied().unwrap_or(0);
+                (limbs[i] >> shift) | (high << (32 - shift))
+            })
+            .collect();
+        BigUint::from_limbs(shifted)
+    }
+}
+
+// The operators above take references so that nothing gets cloned behind the caller's back. These
+// fill in the owned combinations so expressions like `a * b + c` read naturally.
+macro_rules! forward_binop {
+    ($trait:ident, $method:ident) => {
+        impl $trait<BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: BigUint) -> BigUint {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $trait<&BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: &BigUint) -> BigUint {
+                (&self).$method(other)
+            }
+        }
+
+        impl $trait<BigUint> for &BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: BigUint) -> BigUint {
+                self.$method(&other)
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add);
+forward_binop!(Sub, sub);
+forward_binop!(Mul, mul);
+forward_binop!(Div, div);
+forward_binop!(Rem, rem);
+
+impl Shl<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shl(self, bits: usize) -> BigUint {
+        &self << bits
+    }
+}
+
+impl Shr<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: usize) -> BigUint {
+        &self >> bits
+    }
+}
+
+// Shifts left by less than a limb, always adding a limb on top for whatever spills out
+fn shl_limbs(limbs: &[u32], shift: u32) -> Vec<u32> {
+    let mut out = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for &l in limbs {
+        out.push((l << shift) | carry);
+        carry = if shift == 0 { 0 } else { l >> (32 - shift) };
+    }
+    out.push(carry);
+    out
+}
+
+fn divrem_limb(limbs: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut q = vec![0; limbs.len()];
+    let mut r = 0u64;
+    for (i, &l) in limbs.iter().enumerate().rev() {
+        let cur = (r << 32) | l as u64;
+        q[i] = (cur / divisor as u64) as u32;
+        r = cur % divisor as u64;
+    }
+    (q, r as u32)
+}
+
+// Knuth's algorithm D (TAOCP vol. 2, 4.3.1), following the layout in Hacker's Delight. The
+// divisor has at least two limbs and the dividend is at least as long.
+fn divrem_knuth(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let n = v.len();
+    let m = u.len();
+
+    // Normalize so the divisor's top bit is set, which keeps each quotient digit estimate within
+    // two of the real one
+    let shift = v[n - 1].leading_zeros();
+    let vn = shl_limbs(v, shift);
+    let mut un = shl_limbs(u, shift);
+    let base = 1u64 << 32;
+
+    let mut q = vec![0; m - n + 1];
+    for j in (0..=m - n).rev() {
+        let num = ((un[j + n] as u64) << 32) | un[j + n - 1] as u64;
+        let mut qhat = num / vn[n - 1] as u64;
+        let mut rhat = num % vn[n - 1] as u64;
+        while qhat >= base || qhat * vn[n - 2] as u64 > ((rhat << 32) | un[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += vn[n - 1] as u64;
+            if rhat >= base {
+                break;
+            }
+        }
+
+        // Multiply and subtract qhat * v from the current window of u
+        let mut k: i64 = 0;
+        for i in 0..n {
+            let p = qhat * vn[i] as u64;
+            let t = un[i + j] as i64 - k - (p & 0xffff_ffff) as i64;
+            un[i + j] = t as u32;
+            k = (p >> 32) as i64 - (t >> 32);
+        }
+        let t = un[j + n] as i64 - k;
+        un[j + n] = t as u32;
+
+        // qhat was one too big, so add v back
+        q[j] = qhat as u32;
+        if t < 0 {
+            q[j] = q[j].wrapping_sub(1);
+            let mut carry = 0;
+            for i in 0..n {
+                let sum = un[i + j] as u64 + vn[i] as u64 + carry;
+                un[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u32);
+        }
+    }
+
+    // Undo the normalization on what's left
+    let r = (0..n)
+        .map(|i| {
+            if shift == 0 {
+                un[i]
+            } else {
+                (un[i] >> shift) | (un[i + 1] << (32 - shift))
+            }
+        })
+        .collect();
+    (q, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::bn::{BigNum, BigNumContext};
+    use rand::Rng;
+
+    // A pair of the same random number in both representations. The length is random too, so the
+    // tests see short, long and lopsided operands, with the odd run of zero bytes on top.
+    fn random_pair(rng: &mut impl Rng, max_bytes: usize) -> Result<(BigUint, BigNum)> {
+        let len = rng.gen_range(0..=max_bytes);
+        let mut bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        if rng.gen_bool(0.2) {
+            for b in bytes.iter_mut().take(len / 3) {
+                *b = 0;
+            }
+        }
+        Ok((BigUint::from_bytes_be(&bytes), BigNum::from_slice(&bytes)?))
+    }
+
+    fn nonzero_pair(rng: &mut impl Rng, max_bytes: usize) -> Result<(BigUint, BigNum)> {
+        loop {
+            let pair = random_pair(rng, max_bytes)?;
+            if !pair.0.is_zero() {
+                return Ok(pair);
+            }
+        }
+    }
+
+    fn same(ours: &BigUint, theirs: &BigNum) -> bool {
+        ours.to_bytes_be() == theirs.to_vec()
+    }
+
+    #[test]
+    fn hex_and_bytes() -> Result<()> {
+        let n = BigUint::from_hex("123456789abcdef0123")?;
+        assert_eq!(n.to_hex(), "0123456789abcdef0123");
+        assert_eq!(n.to_bytes_be(), hex_to_u8("0123456789abcdef0123")?);
+        assert_eq!(BigUint::from_bytes_be(&n.to_bytes_be()), n);
+        assert_eq!(format!("{:x}", n), "123456789abcdef0123");
+
+        // Leading zeroes don't change the value
+        assert_eq!(BigUint::from_hex("000000ff")?, BigUint::from(255));
+        assert_eq!(BigUint::from_hex("0")?, BigUint::zero());
+        assert_eq!(BigUint::zero().to_hex(), "0");
+        assert!(BigUint::zero().to_bytes_be().is_empty());
+
+        assert!(BigUint::from_hex("xyz").is_err());
+        assert!(BigUint::from_hex("ü").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn decimal_matches_openssl() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(BigUint::from(1_000_000_000).to_string(), "1000000000");
+        for _ in 0..100 {
+            let (a, x) = random_pair(&mut rng, 100)?;
+            assert_eq!(a.to_string(), x.to_dec_str()?.to_string());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn arithmetic_matches_openssl() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut ctx = BigNumContext::new()?;
+        for _ in 0..500 {
+            let (a, x) = random_pair(&mut rng, 200)?;
+            let (b, y) = random_pair(&mut rng, 200)?;
+
+            let mut z = BigNum::new()?;
+            z.checked_add(&x, &y)?;
+            assert!(same(&(&a + &b), &z));
+            z.checked_mul(&x, &y, &mut ctx)?;
+            assert!(same(&(&a * &b), &z));
+
+            assert_eq!(a.cmp(&b), x.cmp(&y));
+            let (big, small, n, m) = if a >= b {
+                (&a, &b, &x, &y)
+            } else {
+                (&b, &a, &y, &x)
+            };
+            z.checked_sub(n, m)?;
+            assert!(same(&(big - small), &z));
+            if big != small {
+                assert_eq!(small.checked_sub(big), None);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn division_matches_openssl() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut ctx = BigNumContext::new()?;
+        for _ in 0..1000 {
+            let (a, x) = random_pair(&mut rng, 200)?;
+            let (b, y) = nonzero_pair(&mut rng, 100)?;
+
+            let (q, r) = a.divrem(&b);
+            let mut z = BigNum::new()?;
+            z.checked_div(&x, &y, &mut ctx)?;
+            assert!(same(&q, &z));
+            z.checked_rem(&x, &y, &mut ctx)?;
+            assert!(same(&r, &z));
+            assert_eq!(&q * &b + &r, a);
+        }
+
+        // A case from Hacker's Delight where the quotient digit estimate is still one too big, so
+        // algorithm D has to add the divisor back
+        let a = BigUint::from_hex("7fffffff800000000000000000000000")?;
+        let b = BigUint::from_hex("800000000000000000000001")?;
+        let (q, r) = a.divrem(&b);
+        assert_eq!(&q * &b + &r, a);
+        assert!(r < b);
+        Ok(())
+    }
+
+    #[test]
+    fn random_below_stays_in_range() {
+        let mut rng = rand::thread_rng();
+        let bound = BigUint::from(1000);
+        let mut seen = [false; 1000];
+        for _ in 0..20000 {
+            let n = BigUint::random_below(&mut rng, &bound);
+            assert!(n < bound);
+            seen[n.to_string().parse::<usize>().unwrap()] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    #[should_panic(expected = "divide by zero")]
+    fn division_by_zero_panics() {
+        let _ = BigUint::one().divrem(&BigUint::zero());
+    }
+
+    #[test]
+    fn shifts_match_openssl() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let (a, x) = random_pair(&mut rng, 100)?;
+            let bits = rng.gen_range(0..300);
+
+            let mut z = BigNum::new()?;
+            z.lshift(&x, bits)?;
+            assert!(same(&(&a << bits as usize), &z));
+            z.rshift(&x, bits)?;
+            assert!(same(&(&a >> bits as usize), &z));
+            assert_eq!(a.bits(), x.num_bits() as usize);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn modpow_matches_openssl() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut ctx = BigNumContext::new()?;
+        for _ in 0..50 {
+            let (base, x) = random_pair(&mut rng, 64)?;
+            let (exp, y) = random_pair(&mut rng, 64)?;
+            let (modulus, m) = nonzero_pair(&mut rng, 64)?;
+
+            let mut z = BigNum::new()?;
+            z.mod_exp(&x, &y, &m, &mut ctx)?;
+            assert!(same(&base.modpow(&exp, &modulus), &z));
+        }
+        assert_eq!(
+            BigUint::from(4).modpow(&BigUint::from(13), &BigUint::from(497)),
+            BigUint::from(445)
+        );
+        assert_eq!(
+            BigUint::from(5).modpow(&BigUint::zero(), &BigUint::one()),
+            BigUint::zero()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn gcd_and_modinv_match_openssl() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut ctx = BigNumContext::new()?;
+        for _ in 0..200 {
+            let (a, x) = nonzero_pair(&mut rng, 64)?;
+            let (m, y) = nonzero_pair(&mut rng, 64)?;
+
+            let mut z = BigNum::new()?;
+            z.gcd(&x, &y, &mut ctx)?;
+            assert!(same(&a.gcd(&m), &z));
+
+            match a.modinv(&m) {
+                Some(inv) => {
+                    z.mod_inverse(&x, &y, &mut ctx)?;
+                    assert!(same(&inv, &z));
+                    assert_eq!(&(&a * &inv) % &m, BigUint::one() % &m);
+                }
+                None => assert_ne!(a.gcd(&m), BigUint::one()),
+            }
+        }
+        assert_eq!(
+            BigUint::from(17).modinv(&BigUint::from(3120)),
+            Some(BigUint::from(2753))
+        );
+        assert_eq!(BigUint::from(6).modinv(&BigUint::from(9)), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use cryptopals::{
+    cipher::gen_aes_128_key,
+    server::{hmac_handler, serve},
+    Result,
+};
+use std::net::TcpListener;
+use std::time::Duration;
+
+// Usage: hmac_server [port] [delay in ms]
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let port: u16 = args.next().map(|p| p.parse()).transpose()?.unwrap_or(9000);
+    let delay: u64 = args.next().map(|d| d.parse()).transpose()?.unwrap_or(50);
+
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!(
+        "listening on {} with a {}ms delay per byte",
+        listener.local_addr()?,
+        delay
+    );
+    let key = gen_aes_128_key().to_vec();
+    serve(listener, hmac_handler(key, Duration::from_millis(delay)))
+}
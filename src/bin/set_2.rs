@@ -1,7 +1,13 @@
 #![allow(dead_code)]
 use cryptopals::{
+    attacks::cbc::{cbc_bitflip_admin, UserdataOracle},
+    attacks::ecb::{byte_at_a_time_ecb, byte_at_a_time_ecb_prefix, cut_and_paste_admin},
     base64::decode_b64,
-    cipher::{add_pkcs7_padding, decrypt, encryption_oracle, CipherMode},
+    cipher::{
+        add_pkcs7_padding, decrypt, encryption_oracle, gen_aes_128_key, strip_pkcs7_padding,
+        suffix_oracle, CipherMode, PaddingError, PrefixOracle,
+    },
+    profile::{decrypt_profile, encrypt_profile},
     Result,
 };
 use std::collections::HashSet;
@@ -41,8 +47,32 @@ fn c11(f: impl Fn(&[u8]) -> Result<Vec<u8>>) -> Result<CipherMode> {
     Ok(CipherMode::CBC)
 }
 
-fn c12() -> Result<()> {
-    Ok(())
+fn c12() -> Result<String> {
+    let key = gen_aes_128_key();
+    let (recovered, _) = byte_at_a_time_ecb(|input| suffix_oracle(input, &key))?;
+    Ok(String::from_utf8(recovered)?)
+}
+
+fn c13() -> Result<Vec<(String, String)>> {
+    let key = gen_aes_128_key();
+    let forged = cut_and_paste_admin(|email| encrypt_profile(email, &key))?;
+    decrypt_profile(&forged, &key)
+}
+
+fn c14() -> Result<String> {
+    let oracle = PrefixOracle::new();
+    let (recovered, _) = byte_at_a_time_ecb_prefix(|input| oracle.encrypt(input))?;
+    Ok(String::from_utf8(recovered)?)
+}
+
+fn c15(input: &[u8]) -> std::result::Result<Vec<u8>, PaddingError> {
+    strip_pkcs7_padding(input, 16)
+}
+
+fn c16() -> Result<bool> {
+    let oracle = UserdataOracle::new();
+    let forged = cbc_bitflip_admin(|input| oracle.encrypt(input))?;
+    oracle.is_admin(&forged)
 }
 
 #[cfg(test)]
@@ -63,4 +93,41 @@ mod set2 {
         assert_eq!(decrypted, expected);
         Ok(())
     }
+
+    #[test]
+    fn challenge_12() -> Result<()> {
+        let recovered = c12()?;
+        assert!(recovered.starts_with("Rollin' in my 5.0\n"));
+        assert!(recovered.ends_with("No, I just drove by\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_13() -> Result<()> {
+        let profile = c13()?;
+        assert!(profile.contains(&("role".to_owned(), "admin".to_owned())));
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_14() -> Result<()> {
+        assert_eq!(c14()?, c12()?);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_15() {
+        assert_eq!(
+            c15(b"ICE ICE BABY\x04\x04\x04\x04"),
+            Ok(b"ICE ICE BABY".to_vec())
+        );
+        assert_eq!(c15(b"ICE ICE BABY\x05\x05\x05\x05"), Err(PaddingError));
+        assert_eq!(c15(b"ICE ICE BABY\x01\x02\x03\x04"), Err(PaddingError));
+    }
+
+    #[test]
+    fn challenge_16() -> Result<()> {
+        assert!(c16()?);
+        Ok(())
+    }
 }
@@ -0,0 +1,140 @@
+#![allow(dead_code)]
+use cryptopals::{
+    attacks::cbc::{padding_oracle_attack, PaddingOracleServer},
+    attacks::mt19937::{
+        crack_mt19937_cipher_seed, crack_timestamp_seed, is_time_seeded_token,
+        mt19937_cipher_oracle, reset_token, seed_with_time_and_wait,
+    },
+    base64::decode_b64,
+    cipher::{decrypt, CipherMode, CtrLayout},
+    rng::{clone_from_outputs, Clock, Mt19937},
+    Result,
+};
+
+fn main() -> Result<()> {
+    let z = c17()?;
+    println!("{}", z);
+    Ok(())
+}
+
+fn c17() -> Result<String> {
+    let server = PaddingOracleServer::new();
+    let (ciphertext, iv) = server.encrypt()?;
+    let recovered = padding_oracle_attack(&server, &iv, &ciphertext)?;
+    Ok(String::from_utf8(recovered)?)
+}
+
+fn c18(input: &str) -> Result<String> {
+    let data = decode_b64(input);
+    let mode = CipherMode::CTR(CtrLayout::LittleEndian64);
+    let decrypted = decrypt(mode, &data, b"YELLOW SUBMARINE", Some(&[0; 8]))?;
+    Ok(String::from_utf8(decrypted)?)
+}
+
+fn c21(seed: u32) -> u32 {
+    Mt19937::new(seed).next_u32()
+}
+
+// Returns the seed recovered from the output, looking back over the worst case of both waits
+fn c22(clock: &impl Clock) -> Option<u32> {
+    let output = seed_with_time_and_wait(clock, &mut rand::thread_rng());
+    let now = clock.now();
+    crack_timestamp_seed(output, now - 2000..=now)
+}
+
+// Clones a generator from its output and returns how many of the next 1000 values it predicted
+fn c23(seed: u32) -> usize {
+    let mut mt = Mt19937::new(seed);
+    let mut outputs = [0; 624];
+    for o in outputs.iter_mut() {
+        *o = mt.next_u32();
+    }
+
+    let mut clone = clone_from_outputs(&outputs);
+    (0..1000)
+        .filter(|_| clone.next_u32() == mt.next_u32())
+        .count()
+}
+
+// Recovers the 16-bit seed, and checks whether a fresh reset token looks time seeded
+fn c24(clock: &impl Clock) -> (Option<u16>, bool) {
+    let seed: u16 = rand::random();
+    let ciphertext = mt19937_cipher_oracle(&b"A".repeat(14), seed);
+    let cracked = crack_mt19937_cipher_seed(&ciphertext, &b"A".repeat(14));
+
+    let token = reset_token(clock);
+    (cracked, is_time_seeded_token(&token, clock, 60))
+}
+
+#[cfg(test)]
+mod set3 {
+    use super::*;
+    use cryptopals::rng::{FakeClock, SystemClock};
+    use std::cell::Cell;
+
+    // A fake clock that remembers the first time it was read, which is when c22 seeds
+    struct SeedWatcher {
+        clock: FakeClock,
+        first_read: Cell<Option<u64>>,
+    }
+
+    impl Clock for SeedWatcher {
+        fn now(&self) -> u64 {
+            let now = self.clock.now();
+            if self.first_read.get().is_none() {
+                self.first_read.set(Some(now));
+            }
+            now
+        }
+
+        fn sleep(&self, secs: u64) {
+            self.clock.sleep(secs);
+        }
+    }
+
+    #[test]
+    fn challenge_17() -> Result<()> {
+        let recovered = c17()?;
+        assert!(recovered.starts_with("00000"));
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_18() -> Result<()> {
+        let decrypted =
+            c18("L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==")?;
+        assert_eq!(
+            decrypted,
+            "Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby "
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_21() {
+        assert_eq!(c21(5489), 3499211612);
+    }
+
+    #[test]
+    fn challenge_22() {
+        let clock = SeedWatcher {
+            clock: FakeClock::new(SystemClock.now()),
+            first_read: Cell::new(None),
+        };
+        let seed = c22(&clock).unwrap();
+        assert_eq!(Some(seed as u64), clock.first_read.get());
+    }
+
+    #[test]
+    fn challenge_23() {
+        assert_eq!(c23(rand::random()), 1000);
+    }
+
+    #[test]
+    fn challenge_24() {
+        let clock = FakeClock::new(SystemClock.now());
+        let (cracked, time_seeded) = c24(&clock);
+        assert!(cracked.is_some());
+        assert!(time_seeded);
+    }
+}
@@ -0,0 +1,126 @@
+#![allow(dead_code)]
+use cryptopals::{
+    attacks::cbc::{recover_key_as_iv, KeyAsIvOracle},
+    attacks::ctr::{ctr_bitflip_admin, recover_ctr_plaintext_via_edit, CtrUserdataOracle},
+    attacks::mac::length_extension,
+    base64::decode_b64,
+    cipher::{ctr_edit, decrypt, encrypt, gen_aes_128_key, CipherMode, CtrLayout},
+    hash::{
+        md4::{md4_prefix_mac, verify_md4_prefix_mac, Md4},
+        sha1::{sha1_prefix_mac, verify_sha1_prefix_mac, Sha1},
+    },
+    Result,
+};
+
+fn main() -> Result<()> {
+    let z = c25("./inputs/s1c7_input.txt")?;
+    println!("{}", z);
+    Ok(())
+}
+
+fn c25(filename: &str) -> Result<String> {
+    let data = decode_b64(&std::fs::read_to_string(filename)?);
+    let plaintext = decrypt(CipherMode::ECB, &data, b"YELLOW SUBMARINE", None)?;
+
+    let key = gen_aes_128_key();
+    let ciphertext = encrypt(
+        CipherMode::CTR(CtrLayout::default()),
+        &plaintext,
+        &key,
+        None,
+    )?;
+    let edit = |ciphertext: &[u8], offset: usize, newtext: &[u8]| {
+        let mut edited = ciphertext.to_vec();
+        ctr_edit(&mut edited, &key, offset, newtext)?;
+        Ok(edited)
+    };
+
+    let recovered = recover_ctr_plaintext_via_edit(&ciphertext, edit)?;
+    Ok(String::from_utf8(recovered)?)
+}
+
+fn c26() -> Result<bool> {
+    let oracle = CtrUserdataOracle::new();
+    let forged = ctr_bitflip_admin(|input| oracle.encrypt(input))?;
+    oracle.is_admin(&forged)
+}
+
+// Recovers the key and uses it to decrypt a fresh ciphertext
+fn c27() -> Result<String> {
+    let oracle = KeyAsIvOracle::new();
+    let key = recover_key_as_iv(|input| oracle.encrypt(input), |input| oracle.decrypt(input))?;
+
+    let ciphertext = oracle.encrypt(b"hello")?;
+    let decrypted = decrypt(CipherMode::CBC, &ciphertext, &key, Some(&key))?;
+    Ok(String::from_utf8(decrypted)?)
+}
+
+fn c28(key: &[u8], message: &[u8]) -> bool {
+    let mac = sha1_prefix_mac(key, message);
+    let mut tampered = message.to_vec();
+    tampered.push(b'!');
+
+    verify_sha1_prefix_mac(key, message, &mac) && !verify_sha1_prefix_mac(key, &tampered, &mac)
+}
+
+fn c29(key: &[u8]) -> Option<Vec<u8>> {
+    let message = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+    let mac = sha1_prefix_mac(key, message);
+
+    let verify = |m: &[u8], mac: &[u8]| verify_sha1_prefix_mac(key, m, mac);
+    length_extension::<Sha1>(message, &mac, b";admin=true", 0..=64, verify)
+        .map(|(forged, _)| forged)
+}
+
+fn c30(key: &[u8]) -> Option<Vec<u8>> {
+    let message = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+    let mac = md4_prefix_mac(key, message);
+
+    let verify = |m: &[u8], mac: &[u8]| verify_md4_prefix_mac(key, m, mac);
+    length_extension::<Md4>(message, &mac, b";admin=true", 0..=64, verify).map(|(forged, _)| forged)
+}
+
+#[cfg(test)]
+mod set4 {
+    use super::*;
+
+    #[test]
+    fn challenge_25() -> Result<()> {
+        let recovered = c25("./inputs/s1c7_input.txt")?;
+        let expected = std::fs::read_to_string("./inputs/s1c6_output.txt")?;
+        assert_eq!(recovered, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_26() -> Result<()> {
+        assert!(c26()?);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_27() -> Result<()> {
+        assert_eq!(
+            c27()?,
+            "comment1=cooking%20MCs;userdata=hello;comment2=%20like%20a%20pound%20of%20bacon"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_28() {
+        assert!(c28(b"YELLOW SUBMARINE", b"the message"));
+    }
+
+    #[test]
+    fn challenge_29() {
+        let forged = c29(b"tangerine").unwrap();
+        assert!(forged.ends_with(b";admin=true"));
+    }
+
+    #[test]
+    fn challenge_30() {
+        let forged = c30(b"tangerine").unwrap();
+        assert!(forged.ends_with(b";admin=true"));
+    }
+}
@@ -0,0 +1,147 @@
+#![allow(dead_code)]
+use cryptopals::{
+    attacks::{
+        dh::{KeyFixingMitm, MaliciousGenerator, MaliciousGroupMitm},
+        srp::zero_key_login,
+    },
+    dh::{derive_aes_key, Group},
+    protocol::{
+        dh_echo::{EchoBot, EchoClient},
+        dh_negotiated::{NegotiatingBot, NegotiatingClient},
+        run, Direction,
+    },
+    server,
+    srp::{login, login_handler, Params, Verifier},
+    Result,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+fn main() -> Result<()> {
+    let key = c33();
+    println!("{:02x?}", key);
+    Ok(())
+}
+
+// Both sides of a Diffie-Hellman exchange over the 1536-bit MODP group, returning each one's
+// derived key
+fn c33() -> ([u8; 16], [u8; 16]) {
+    let group = Group::modp_1536();
+    let alice = group.generate_keypair();
+    let bob = group.generate_keypair();
+
+    (
+        derive_aes_key(&alice.shared_secret(&bob.public)),
+        derive_aes_key(&bob.shared_secret(&alice.public)),
+    )
+}
+
+// Runs the echo protocol with a key-fixing MITM in the middle and returns what it read
+fn c34(messages: &[&str]) -> Result<Vec<(Direction, Vec<u8>)>> {
+    let messages = messages.iter().map(|m| m.as_bytes().to_vec()).collect();
+    let client = EchoClient::new(Group::modp_1536(), messages);
+    let mut mitm = KeyFixingMitm::new();
+    run(client, EchoBot, &mut mitm)?;
+    Ok(mitm.recovered)
+}
+
+// Runs the negotiated echo protocol with the given generator slipped in and returns what the
+// middle read
+fn c35(generator: MaliciousGenerator, messages: &[&str]) -> Result<Vec<(Direction, Vec<u8>)>> {
+    let messages = messages.iter().map(|m| m.as_bytes().to_vec()).collect();
+    let client = NegotiatingClient::new(Group::modp_1536(), messages);
+    let mut mitm = MaliciousGroupMitm::new(generator);
+    run(client, NegotiatingBot, &mut mitm)?;
+    Ok(mitm.recovered)
+}
+
+// Starts an SRP server on localhost with a single user registered
+fn c36_server(email: &str, password: &str) -> Result<(SocketAddr, Params)> {
+    let params = Params::default();
+    let users = HashMap::from([(email.to_owned(), Verifier::new(&params, email, password))]);
+    Ok((server::spawn(login_handler(params.clone(), users))?, params))
+}
+
+// Logs in to the server the honest way
+fn c36(addr: SocketAddr, params: &Params, email: &str, password: &str) -> Result<bool> {
+    login(addr, params, email, password)
+}
+
+// Logs in to the server without the password, sending 0, N and 2N as the public key. Returns
+// whether each got in.
+fn c37(addr: SocketAddr, params: &Params, email: &str) -> Result<Vec<bool>> {
+    [0, 1, 2]
+        .into_iter()
+        .map(|multiple| zero_key_login(addr, params, email, multiple))
+        .collect()
+}
+
+#[cfg(test)]
+mod set5 {
+    use super::*;
+    use cryptopals::bignum::BigUint;
+
+    #[test]
+    fn challenge_33() {
+        let group = Group::new(BigUint::from(37), BigUint::from(5));
+        let alice = group.generate_keypair();
+        let bob = group.generate_keypair();
+        assert_eq!(
+            alice.shared_secret(&bob.public),
+            bob.shared_secret(&alice.public)
+        );
+
+        let (alice_key, bob_key) = c33();
+        assert_eq!(alice_key, bob_key);
+    }
+
+    #[test]
+    fn challenge_34() -> Result<()> {
+        let recovered = c34(&["hello bob", "it's alice"])?;
+        let plaintexts: Vec<&[u8]> = recovered.iter().map(|(_, m)| &m[..]).collect();
+        assert_eq!(
+            plaintexts,
+            vec![
+                &b"hello bob"[..],
+                b"hello bob",
+                b"it's alice",
+                b"it's alice"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_35() -> Result<()> {
+        for generator in [
+            MaliciousGenerator::One,
+            MaliciousGenerator::P,
+            MaliciousGenerator::PMinusOne,
+        ] {
+            let recovered = c35(generator, &["g is for group"])?;
+            assert_eq!(
+                recovered,
+                vec![
+                    (Direction::AToB, b"g is for group".to_vec()),
+                    (Direction::BToA, b"g is for group".to_vec())
+                ]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_36() -> Result<()> {
+        let (addr, params) = c36_server("bob@example.com", "swordfish")?;
+        assert!(c36(addr, &params, "bob@example.com", "swordfish")?);
+        assert!(!c36(addr, &params, "bob@example.com", "trout")?);
+        Ok(())
+    }
+
+    #[test]
+    fn challenge_37() -> Result<()> {
+        let (addr, params) = c36_server("bob@example.com", "swordfish")?;
+        assert_eq!(c37(addr, &params, "bob@example.com")?, vec![true; 3]);
+        Ok(())
+    }
+}
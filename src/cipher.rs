@@ -3,14 +3,42 @@ use openssl::symm::{Cipher, Crypter, Mode};
 use rand::Rng;
 
 use crate::{calculate_fitting_quotient, normalized_hamming_distance, Result};
+use std::error::Error;
+use std::fmt;
 use std::str;
 
 #[derive(Debug)]
 pub enum CipherMode {
     ECB,
     CBC,
+    CTR(CtrLayout),
 }
 
+// How the nonce and counter are laid out in each CTR keystream block
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CtrLayout {
+    // 64-bit little-endian nonce followed by a 64-bit little-endian block counter, as used by
+    // cryptopals. The IV is the 8 byte nonce.
+    #[default]
+    LittleEndian64,
+    // The whole block is a 128-bit big-endian counter, as in NIST SP 800-38A. The IV is the 16 byte
+    // initial counter block.
+    BigEndian128,
+}
+
+// Returned when decrypted data does not end in valid PKCS#7 padding. Kept separate from the other
+// errors so it can be told apart with `downcast_ref`.
+#[derive(Debug, PartialEq)]
+pub struct PaddingError;
+
+impl fmt::Display for PaddingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error: invalid PKCS#7 padding")
+    }
+}
+
+impl Error for PaddingError {}
+
 pub fn make_single_byte_xor(input: &[u8], key: u8) -> Vec<u8> {
     // XOR each byte in input with the key
     input.iter().map(|x| x ^ key).collect()
@@ -64,10 +92,15 @@ pub fn find_repeating_xor_size(input: &[u8]) -> usize {
 
 pub fn find_repeating_xor_key(input: &[u8]) -> Vec<u8> {
     let possible_keysize = find_repeating_xor_size(input);
+    find_repeating_xor_key_with_size(input, possible_keysize)
+}
+
+// Same as `find_repeating_xor_key` for when the key size is already known
+pub fn find_repeating_xor_key_with_size(input: &[u8], keysize: usize) -> Vec<u8> {
     let mut key: Vec<u8> = Vec::new();
 
     let mut chunked: Vec<Vec<u8>> = Vec::new();
-    for i in input.chunks(possible_keysize) {
+    for i in input.chunks(keysize) {
         chunked.push(i.to_vec());
     }
 
@@ -117,13 +150,9 @@ fn encrypt_aes_128_cbc(input: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>> {
     let mut buf: Vec<u8> = vec![];
     let mut last = iv.to_vec();
 
-    for chunk in input.chunks(16) {
-        let mut start = chunk.to_vec();
-        if chunk.len() < 16 {
-            start = add_pkcs7_padding(&start, Cipher::aes_128_ecb().block_size())?;
-        }
-
-        start = make_repeating_xor(&start, &last);
+    let padded = add_pkcs7_padding(input, Cipher::aes_128_ecb().block_size())?;
+    for chunk in padded.chunks(16) {
+        let start = make_repeating_xor(chunk, &last);
         last = encrypt_aes_128(&start, key)?;
         buf.extend(&last);
     }
@@ -142,20 +171,110 @@ fn decrypt_aes_128_cbc(input: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>> {
         buf.extend(&to_plain);
     }
 
-    // remove padding
-    let pad = *buf.last().ok_or("unavailable")? as usize;
-    buf.truncate(buf.len() - pad);
-    Ok(buf)
+    Ok(strip_pkcs7_padding(
+        &buf,
+        Cipher::aes_128_ecb().block_size(),
+    )?)
 }
 
-fn gen_aes_128_key() -> [u8; 16] {
+// Builds the counter block for the given block number
+fn ctr_block(iv: &[u8], layout: CtrLayout, block: u64) -> Result<[u8; 16]> {
+    let mut counter = [0; 16];
+    match layout {
+        CtrLayout::LittleEndian64 => {
+            if iv.len() != 8 {
+                return Err("error: CTR nonce must be 8 bytes".into());
+            }
+            counter[..8].copy_from_slice(iv);
+            counter[8..].copy_from_slice(&block.to_le_bytes());
+        }
+        CtrLayout::BigEndian128 => {
+            let start: [u8; 16] = iv
+                .try_into()
+                .map_err(|_| "error: CTR initial counter block must be 16 bytes")?;
+            let count = u128::from_be_bytes(start).wrapping_add(block as u128);
+            counter.copy_from_slice(&count.to_be_bytes());
+        }
+    }
+    Ok(counter)
+}
+
+// `len` bytes of CTR keystream starting `offset` bytes into the stream. Without an IV the
+// nonce/counter starts at zero.
+fn ctr_keystream(
+    key: &[u8],
+    iv: Option<&[u8]>,
+    layout: CtrLayout,
+    offset: usize,
+    len: usize,
+) -> Result<Vec<u8>> {
+    let iv = iv.unwrap_or(match layout {
+        CtrLayout::LittleEndian64 => &[0; 8],
+        CtrLayout::BigEndian128 => &[0; 16],
+    });
+    let first = offset / 16;
+    let last = (offset + len).div_ceil(16);
+
+    let mut keystream: Vec<u8> = Vec::with_capacity((last - first) * 16);
+    for block in first..last {
+        keystream.extend(encrypt_aes_128(&ctr_block(iv, layout, block as u64)?, key)?);
+    }
+    let start = offset % 16;
+    Ok(keystream[start..start + len].to_vec())
+}
+
+// CTR turns the block cipher into a stream cipher, so encrypting and decrypting are the same XOR
+// against the keystream and no padding is needed
+fn aes_128_ctr(input: &[u8], key: &[u8], iv: Option<&[u8]>, layout: CtrLayout) -> Result<Vec<u8>> {
+    let keystream = ctr_keystream(key, iv, layout, 0, input.len())?;
+    Ok(make_repeating_xor(input, &keystream))
+}
+
+// Overwrites the plaintext under a CTR ciphertext starting at `offset`, leaving the rest alone.
+// Writing past the end grows the ciphertext, but `offset` can't leave a gap. Uses the default
+// layout with a zero nonce, see `ctr_edit_with` otherwise.
+pub fn ctr_edit(ciphertext: &mut Vec<u8>, key: &[u8], offset: usize, newtext: &[u8]) -> Result<()> {
+    ctr_edit_with(ciphertext, key, None, CtrLayout::default(), offset, newtext)
+}
+
+pub fn ctr_edit_with(
+    ciphertext: &mut Vec<u8>,
+    key: &[u8],
+    iv: Option<&[u8]>,
+    layout: CtrLayout,
+    offset: usize,
+    newtext: &[u8],
+) -> Result<()> {
+    if offset > ciphertext.len() {
+        return Err("error: edit offset is past the end of the ciphertext".into());
+    }
+    let keystream = ctr_keystream(key, iv, layout, offset, newtext.len())?;
+    let edited = make_repeating_xor(newtext, &keystream);
+
+    let overlap = usize::min(edited.len(), ciphertext.len() - offset);
+    ciphertext[offset..offset + overlap].copy_from_slice(&edited[..overlap]);
+    ciphertext.extend_from_slice(&edited[overlap..]);
+    Ok(())
+}
+
+pub fn gen_aes_128_key() -> [u8; 16] {
+    gen_aes_128_key_with(&mut rand::thread_rng())
+}
+
+// Same as `gen_aes_128_key` but drawing from the given generator, e.g. a seeded `Mt19937`
+pub fn gen_aes_128_key_with(rng: &mut impl Rng) -> [u8; 16] {
     let mut v = [0; 16];
-    rand::thread_rng().fill(&mut v);
+    rng.fill(&mut v);
     v
 }
 
 pub fn encryption_oracle(input: &[u8]) -> Result<Vec<u8>> {
-    let mut rng = rand::thread_rng();
+    encryption_oracle_with(input, &mut rand::thread_rng())
+}
+
+// Same as `encryption_oracle` but drawing from the given generator, so a seeded one makes the
+// choices repeatable
+pub fn encryption_oracle_with(input: &[u8], rng: &mut impl Rng) -> Result<Vec<u8>> {
     let fill_before: u8 = rng.gen_range(5..=10);
     let fill_after: u8 = rng.gen_range(5..=10);
     let mode = if rng.gen::<bool>() {
@@ -165,7 +284,7 @@ pub fn encryption_oracle(input: &[u8]) -> Result<Vec<u8>> {
     };
 
     let mut start: Vec<u8> = vec![];
-    let key = gen_aes_128_key();
+    let key = gen_aes_128_key_with(rng);
     let mut iv = [0; 16];
     rng.fill(&mut iv);
 
@@ -204,57 +323,145 @@ pub fn oracle_two(input: &[u8], key: &[u8]) -> Result<Vec<u8>> {
     encrypt(mode, &start, key, None)
 }
 
+pub(crate) const UNKNOWN_STRING: &str =
+    "Um9sbGluJyBpbiBteSA1LjAKV2l0aCBteSByYWctdG9wIGRvd24gc28gbXkg\
+    aGFpciBjYW4gYmxvdwpUaGUgZ2lybGllcyBvbiBzdGFuZGJ5IHdhdmluZyBq\
+    dXN0IHRvIHNheSBoaQpEaWQgeW91IHN0b3A/IE5vLCBJIGp1c3QgZHJvdmUg\
+    YnkK";
+
+// AES-128-ECB(your-string || unknown-string, random-key)
+// The same secret as `oracle_two`, without the random bytes that one wraps around the input on
+// every call
+pub fn suffix_oracle(input: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    let mut start = input.to_vec();
+    start.extend(decode_b64(UNKNOWN_STRING));
+
+    encrypt(CipherMode::ECB, &start, key, None)
+}
+
+// AES-128-ECB(random-prefix || your-string || unknown-string, random-key)
+// Unlike `oracle_two`, the key and prefix are picked once and stay the same for every call.
+pub struct PrefixOracle {
+    key: [u8; 16],
+    prefix: Vec<u8>,
+}
+
+impl PrefixOracle {
+    pub fn new() -> Self {
+        let len = rand::thread_rng().gen_range(0..48);
+        Self::with_prefix_len(len)
+    }
+
+    pub fn with_prefix_len(len: usize) -> Self {
+        let mut rng = rand::thread_rng();
+        PrefixOracle {
+            key: gen_aes_128_key(),
+            prefix: (0..len).map(|_| rng.gen()).collect(),
+        }
+    }
+
+    pub fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let mut start = self.prefix.clone();
+        start.extend(input);
+        start.extend(decode_b64(UNKNOWN_STRING));
+
+        encrypt(CipherMode::ECB, &start, &self.key, None)
+    }
+}
+
+impl Default for PrefixOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub fn encrypt(mode: CipherMode, input: &[u8], key: &[u8], iv: Option<&[u8]>) -> Result<Vec<u8>> {
     match mode {
         CipherMode::ECB => {
             let mut buf: Vec<u8> = vec![];
-            for chunk in input.chunks(16) {
-                let mut start = chunk.to_vec();
-                if chunk.len() < 16 {
-                    start = add_pkcs7_padding(&start, Cipher::aes_128_ecb().block_size())?;
-                }
-                start = encrypt_aes_128(&start, key)?;
-                buf.extend(&start);
+            let padded = add_pkcs7_padding(input, Cipher::aes_128_ecb().block_size())?;
+            for chunk in padded.chunks(16) {
+                buf.extend(&encrypt_aes_128(chunk, key)?);
             }
             Ok(buf)
         }
         CipherMode::CBC => encrypt_aes_128_cbc(input, key, iv.unwrap()),
+        CipherMode::CTR(layout) => aes_128_ctr(input, key, iv, layout),
     }
 }
 
 pub fn decrypt(mode: CipherMode, input: &[u8], key: &[u8], iv: Option<&[u8]>) -> Result<Vec<u8>> {
+    let block_size = Cipher::aes_128_ecb().block_size();
+    if matches!(mode, CipherMode::ECB | CipherMode::CBC) && !input.len().is_multiple_of(block_size)
+    {
+        return Err("error: ciphertext is not a whole number of blocks".into());
+    }
+    if matches!(mode, CipherMode::CBC) {
+        match iv {
+            None => return Err("error: CBC needs an IV".into()),
+            Some(iv) if iv.len() != block_size => {
+                return Err("error: IV must be one block long".into())
+            }
+            Some(_) => {}
+        }
+    }
+
     match mode {
         CipherMode::ECB => {
             let mut buf: Vec<u8> = vec![];
             for chunk in input.chunks(16) {
                 buf.extend(&decrypt_aes_128(chunk, key)?);
             }
-            // remove padding
-            let pad = *buf.last().ok_or("unavailable")? as usize;
-            buf.truncate(buf.len() - pad);
-            Ok(buf)
+            Ok(strip_pkcs7_padding(
+                &buf,
+                Cipher::aes_128_ecb().block_size(),
+            )?)
         }
         CipherMode::CBC => decrypt_aes_128_cbc(input, key, iv.unwrap()),
+        CipherMode::CTR(layout) => aes_128_ctr(input, key, iv, layout),
     }
 }
 
-// TODO: easier to just return a new vec with padding added
-// than to pull old one I guess
+// Pads to the next multiple of the block size. Input that is already aligned gets a full block of
+// padding, so there is always at least one pad byte to strip.
 pub fn add_pkcs7_padding(src: &[u8], block_size: usize) -> Result<Vec<u8>> {
-    let padding = block_size - (src.len() % block_size);
-    if src.len() > block_size {
-        return Err("error: padding length smaller than src length".into());
+    if block_size == 0 || block_size > 255 {
+        return Err("error: PKCS#7 block size must be between 1 and 255".into());
     }
-    let mut new = Vec::new();
+    let padding = block_size - (src.len() % block_size);
+    let mut new = Vec::with_capacity(src.len() + padding);
     new.extend_from_slice(src);
-    new.resize(block_size, padding as u8);
+    new.resize(src.len() + padding, padding as u8);
 
     Ok(new)
 }
 
+// Every pad byte has to hold the pad length, and the pad length has to be between 1 and the block
+// size
+pub fn strip_pkcs7_padding(
+    src: &[u8],
+    block_size: usize,
+) -> std::result::Result<Vec<u8>, PaddingError> {
+    if src.is_empty() || !src.len().is_multiple_of(block_size) {
+        return Err(PaddingError);
+    }
+    let pad = *src.last().ok_or(PaddingError)?;
+    if pad == 0 || pad as usize > block_size {
+        return Err(PaddingError);
+    }
+
+    let (data, padding) = src.split_at(src.len() - pad as usize);
+    if padding.iter().any(|&p| p != pad) {
+        return Err(PaddingError);
+    }
+    Ok(data.to_vec())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hex_to_u8;
+    use crate::rng::Mt19937;
 
     #[test]
     fn pkcs7_pad() -> Result<()> {
@@ -265,6 +472,91 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn pkcs7_pad_any_length() -> Result<()> {
+        let to_pad = b"The quick brown fox jumps over the lazy dog";
+        let result = add_pkcs7_padding(to_pad, 16)?;
+        assert_eq!(result.len(), 48);
+        assert_eq!(&result[..to_pad.len()], to_pad);
+        assert_eq!(&result[to_pad.len()..], b"\x05".repeat(5));
+
+        let aligned = add_pkcs7_padding(b"YELLOW SUBMARINE", 16)?;
+        assert_eq!(aligned, [&b"YELLOW SUBMARINE"[..], &[16; 16]].concat());
+        assert_eq!(add_pkcs7_padding(b"", 16)?, [16; 16]);
+        assert!(add_pkcs7_padding(b"", 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn pkcs7_strip() {
+        assert_eq!(
+            strip_pkcs7_padding(b"ICE ICE BABY\x04\x04\x04\x04", 16),
+            Ok(b"ICE ICE BABY".to_vec())
+        );
+        assert_eq!(
+            strip_pkcs7_padding(&b"\x10".repeat(16), 16),
+            Ok(b"".to_vec())
+        );
+        assert_eq!(
+            strip_pkcs7_padding(b"ICE ICE BABY\x05\x05\x05\x05", 16),
+            Err(PaddingError)
+        );
+        assert_eq!(
+            strip_pkcs7_padding(b"ICE ICE BABY\x01\x02\x03\x04", 16),
+            Err(PaddingError)
+        );
+        assert_eq!(
+            strip_pkcs7_padding(b"ICE ICE BABY\x00\x00\x00\x00", 16),
+            Err(PaddingError)
+        );
+        assert_eq!(
+            strip_pkcs7_padding(&b"\x11".repeat(32), 16),
+            Err(PaddingError)
+        );
+        assert_eq!(strip_pkcs7_padding(b"ICE ICE BABY", 16), Err(PaddingError));
+    }
+
+    #[test]
+    fn decrypt_partial_block() {
+        let key = b"YELLOW SUBMARINE";
+        let iv = b"\x00".repeat(16);
+        let ciphertext = [0; 33];
+        assert!(decrypt(CipherMode::ECB, &ciphertext, key, None).is_err());
+        assert!(decrypt(CipherMode::CBC, &ciphertext, key, Some(&iv)).is_err());
+        assert!(decrypt(CipherMode::CBC, &ciphertext[..32], key, Some(&iv[..8])).is_err());
+        assert!(decrypt(CipherMode::CBC, &ciphertext[..32], key, None).is_err());
+    }
+
+    #[test]
+    fn decrypt_bad_padding() -> Result<()> {
+        let key = b"YELLOW SUBMARINE";
+        let iv = b"\x00".repeat(16);
+        let ciphertext = encrypt(CipherMode::CBC, b"ICE ICE BABY", key, Some(&iv))?;
+
+        // Flipping the IV flips the same bits of the plaintext, turning the last \x04 into \x09
+        let mut bad_iv = iv.clone();
+        bad_iv[15] ^= 0x04 ^ 0x09;
+        let err = decrypt(CipherMode::CBC, &ciphertext, key, Some(&bad_iv)).unwrap_err();
+        assert!(err.downcast_ref::<PaddingError>().is_some());
+
+        let decrypted = decrypt(CipherMode::CBC, &ciphertext, key, Some(&iv))?;
+        assert_eq!(decrypted, b"ICE ICE BABY");
+        Ok(())
+    }
+
+    #[test]
+    fn seeded_encryption_oracle() -> Result<()> {
+        let input = b"A".repeat(64);
+        let first = encryption_oracle_with(&input, &mut Mt19937::new(1234))?;
+        let second = encryption_oracle_with(&input, &mut Mt19937::new(1234))?;
+        assert_eq!(first, second);
+        assert_eq!(
+            gen_aes_128_key_with(&mut Mt19937::new(1)),
+            gen_aes_128_key_with(&mut Mt19937::new(1))
+        );
+        Ok(())
+    }
+
     #[test]
     fn encrypt_decrypt_aes_128() -> Result<()> {
         let key = b"YELLOW SUBMARINE";
@@ -300,6 +592,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn aligned_input_roundtrip() -> Result<()> {
+        let key = b"YELLOW SUBMARINE";
+        let iv = b"\x00".repeat(16);
+        let message = b"exactly thirty-two bytes long!!!";
+
+        let ecb = encrypt(CipherMode::ECB, message, key, None)?;
+        assert_eq!(
+            ecb,
+            openssl::symm::encrypt(Cipher::aes_128_ecb(), key, None, message)?
+        );
+        assert_eq!(decrypt(CipherMode::ECB, &ecb, key, None)?, message);
+
+        let cbc = encrypt(CipherMode::CBC, message, key, Some(&iv))?;
+        assert_eq!(
+            cbc,
+            openssl::symm::encrypt(Cipher::aes_128_cbc(), key, Some(&iv), message)?
+        );
+        assert_eq!(decrypt(CipherMode::CBC, &cbc, key, Some(&iv))?, message);
+        Ok(())
+    }
+
     #[test]
     fn encrypt_decrypt_aes_128_cbc() -> Result<()> {
         let key = b"YELLOW SUBMARINE";
@@ -309,4 +623,98 @@ mod tests {
         assert_eq!(&result, message);
         Ok(())
     }
+
+    #[test]
+    fn decrypt_aes_128_ctr() -> Result<()> {
+        let data =
+            decode_b64("L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==");
+        let key = b"YELLOW SUBMARINE";
+        let result = decrypt(CipherMode::CTR(CtrLayout::default()), &data, key, None)?;
+        assert_eq!(
+            str::from_utf8(&result)?,
+            "Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby "
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn compare_openssl_aes_128_ctr_little_endian() -> Result<()> {
+        let text = b"The quick brown fox jumps over the lazy dog";
+        let key = b"YELLOW SUBMARINE";
+        let nonce = 0x0123456789abcdefu64.to_le_bytes();
+
+        let mode = CipherMode::CTR(CtrLayout::LittleEndian64);
+        let ciphertext = encrypt(mode, text, key, Some(&nonce))?;
+        // OpenSSL counts big-endian, so check each block against its own counter block
+        for (i, (chunk, expected)) in text.chunks(16).zip(ciphertext.chunks(16)).enumerate() {
+            let mut iv = nonce.to_vec();
+            iv.extend((i as u64).to_le_bytes());
+            let compare = openssl::symm::encrypt(Cipher::aes_128_ctr(), key, Some(&iv), chunk)?;
+            assert_eq!(expected, &compare[..]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn edit_aes_128_ctr() -> Result<()> {
+        let key = b"YELLOW SUBMARINE";
+        let message = b"The quick brown fox jumps over the lazy dog";
+        let mode = || CipherMode::CTR(CtrLayout::default());
+        let original = encrypt(mode(), message, key, None)?;
+
+        // Straddles a block boundary
+        let mut ciphertext = original.clone();
+        ctr_edit(&mut ciphertext, key, 10, b"green cat")?;
+        assert_eq!(ciphertext.len(), original.len());
+        assert_eq!(ciphertext[..10], original[..10]);
+        assert_eq!(ciphertext[19..], original[19..]);
+        assert_eq!(
+            decrypt(mode(), &ciphertext, key, None)?,
+            b"The quick green cat jumps over the lazy dog"
+        );
+
+        // Runs off the end
+        ctr_edit(&mut ciphertext, key, 42, b"g and the fox")?;
+        assert_eq!(
+            decrypt(mode(), &ciphertext, key, None)?,
+            b"The quick green cat jumps over the lazy dog and the fox"
+        );
+
+        assert!(ctr_edit(&mut ciphertext, key, 100, b"gap").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn edit_aes_128_ctr_with_iv() -> Result<()> {
+        let key = b"YELLOW SUBMARINE";
+        let iv = hex_to_u8("f0f1f2f3f4f5f6f7fffffffffffffffe")?;
+        let layout = CtrLayout::BigEndian128;
+        let mut ciphertext = encrypt(CipherMode::CTR(layout), &[0; 40], key, Some(&iv))?;
+
+        ctr_edit_with(&mut ciphertext, key, Some(&iv), layout, 30, b"hello")?;
+        let decrypted = decrypt(CipherMode::CTR(layout), &ciphertext, key, Some(&iv))?;
+        assert_eq!(&decrypted[30..35], b"hello");
+        assert!(decrypted[..30]
+            .iter()
+            .chain(&decrypted[35..])
+            .all(|&b| b == 0));
+        Ok(())
+    }
+
+    #[test]
+    fn compare_openssl_aes_128_ctr_big_endian() -> Result<()> {
+        let text = b"The quick brown fox jumps over the lazy dog, twice over the lazy dog";
+        let key = b"YELLOW SUBMARINE";
+        // Close enough to the edge that the counter carries into the upper 64 bits
+        let iv = hex_to_u8("f0f1f2f3f4f5f6f7fffffffffffffffe")?;
+
+        let mode = CipherMode::CTR(CtrLayout::BigEndian128);
+        let ciphertext = encrypt(mode, text, key, Some(&iv))?;
+        let compare = openssl::symm::encrypt(Cipher::aes_128_ctr(), key, Some(&iv), text)?;
+        assert_eq!(ciphertext, compare);
+
+        let mode = CipherMode::CTR(CtrLayout::BigEndian128);
+        assert_eq!(decrypt(mode, &ciphertext, key, Some(&iv))?, text);
+        Ok(())
+    }
 }
@@ -0,0 +1,208 @@
+use crate::bignum::BigUint;
+use crate::cipher::{decrypt, encrypt, CipherMode};
+use crate::hash::sha1::sha1;
+use crate::Result;
+use rand::Rng;
+
+// RFC 3526 section 2, the 1536-bit MODP group. This is the prime the cryptopals challenges use.
+const MODP_1536_PRIME: &str = "
+    ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22514a08798e3404dd
+    ef9519b3cd3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7ed
+    ee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf0598da48361c55d39a69163fa8fd24cf5f
+    83655d23dca3ad961c62f356208552bb9ed529077096966d670c354e4abc9804f1746c08ca237327ffffffffffffffff";
+
+// RFC 3526 section 3, the 2048-bit MODP group
+const MODP_2048_PRIME: &str = "
+    ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22514a08798e3404dd
+    ef9519b3cd3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7ed
+    ee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf0598da48361c55d39a69163fa8fd24cf5f
+    83655d23dca3ad961c62f356208552bb9ed529077096966d670c354e4abc9804f1746c08ca18217c32905e462e36ce3b
+    e39e772c180e86039b2783a2ec07a28fb5c55df06f4c52c9de2bcbf6955817183995497cea956ae515d2261898fa0510
+    15728e5a8aacaa68ffffffffffffffff";
+
+// A Diffie-Hellman group: the prime modulus and the generator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Group {
+    pub p: BigUint,
+    pub g: BigUint,
+}
+
+impl Group {
+    pub fn new(p: BigUint, g: BigUint) -> Self {
+        Group { p, g }
+    }
+
+    // For a group that came from the other side: p has to leave room for a private key and g has
+    // to be a residue other than 0 or 1, whose powers are all the same
+    pub fn checked(p: BigUint, g: BigUint) -> Result<Self> {
+        let group = Self::with_checked_modulus(p, g)?;
+        if group.g <= BigUint::one() || group.g >= group.p {
+            return Err("error: generator is out of range".into());
+        }
+        Ok(group)
+    }
+
+    // Only the part of `checked` that key generation can't do without, for protocols that
+    // deliberately take g on trust
+    pub fn with_checked_modulus(p: BigUint, g: BigUint) -> Result<Self> {
+        if p <= BigUint::from(2) {
+            return Err("error: modulus is too small to pick a private key from".into());
+        }
+        Ok(Group::new(p, g))
+    }
+
+    pub fn modp_1536() -> Self {
+        Self::from_rfc_prime(MODP_1536_PRIME)
+    }
+
+    pub fn modp_2048() -> Self {
+        Self::from_rfc_prime(MODP_2048_PRIME)
+    }
+
+    // Both RFC 3526 groups use 2 as the generator
+    fn from_rfc_prime(prime: &str) -> Self {
+        let hex: String = prime.split_whitespace().collect();
+        let p = BigUint::from_hex(&hex).expect("RFC 3526 primes are valid hex");
+        Group::new(p, BigUint::from(2))
+    }
+
+    pub fn generate_keypair(&self) -> KeyPair {
+        self.generate_keypair_with(&mut rand::thread_rng())
+    }
+
+    // The private key is picked from [1, p - 1)
+    pub fn generate_keypair_with(&self, rng: &mut impl Rng) -> KeyPair {
+        let private = BigUint::random_below(rng, &(&self.p - BigUint::from(2))) + BigUint::one();
+        let public = self.g.modpow(&private, &self.p);
+        KeyPair {
+            p: self.p.clone(),
+            private,
+            public,
+        }
+    }
+}
+
+pub struct KeyPair {
+    p: BigUint,
+    private: BigUint,
+    pub public: BigUint,
+}
+
+impl KeyPair {
+    // The other side's public key raised to our private key. Both ends land on g^ab mod p.
+    pub fn shared_secret(&self, other_public: &BigUint) -> BigUint {
+        other_public.modpow(&self.private, &self.p)
+    }
+}
+
+// AES-128 key from the first 16 bytes of SHA1 over the shared secret's big-endian bytes
+pub fn derive_aes_key(secret: &BigUint) -> [u8; 16] {
+    let mut key = [0; 16];
+    key.copy_from_slice(&sha1(&secret.to_bytes_be())[..16]);
+    key
+}
+
+// Message format for the cryptopals protocols: AES-CBC under the derived key with a fresh random
+// IV, which gets sent along after the ciphertext
+pub fn encrypt_message(key: &[u8; 16], message: &[u8]) -> Result<Vec<u8>> {
+    let iv: [u8; 16] = rand::thread_rng().gen();
+    let mut data = encrypt(CipherMode::CBC, message, key, Some(&iv))?;
+    data.extend_from_slice(&iv);
+    Ok(data)
+}
+
+pub fn decrypt_message(key: &[u8; 16], data: &[u8]) -> Result<Vec<u8>> {
+    if data.len() < 32 {
+        return Err("error: message is too short to hold a block and an IV".into());
+    }
+    if !(data.len() - 16).is_multiple_of(16) {
+        return Err("error: message is not a whole number of blocks and an IV".into());
+    }
+    let (ciphertext, iv) = data.split_at(data.len() - 16);
+    decrypt(CipherMode::CBC, ciphertext, key, Some(iv))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::bn::BigNum;
+
+    #[test]
+    fn rfc_groups_match_openssl() -> Result<()> {
+        assert_eq!(
+            Group::modp_1536().p.to_bytes_be(),
+            BigNum::get_rfc3526_prime_1536()?.to_vec()
+        );
+        assert_eq!(
+            Group::modp_2048().p.to_bytes_be(),
+            BigNum::get_rfc3526_prime_2048()?.to_vec()
+        );
+        assert_eq!(Group::modp_1536().g, BigUint::from(2));
+        Ok(())
+    }
+
+    #[test]
+    fn small_group_agrees() {
+        let group = Group::new(BigUint::from(37), BigUint::from(5));
+        for _ in 0..100 {
+            let alice = group.generate_keypair();
+            let bob = group.generate_keypair();
+            assert!(alice.public < group.p);
+            assert_eq!(
+                alice.shared_secret(&bob.public),
+                bob.shared_secret(&alice.public)
+            );
+        }
+    }
+
+    #[test]
+    fn parties_exchange_cbc_message() -> Result<()> {
+        let group = Group::modp_1536();
+        let alice = group.generate_keypair();
+        let bob = group.generate_keypair();
+
+        let alice_key = derive_aes_key(&alice.shared_secret(&bob.public));
+        let bob_key = derive_aes_key(&bob.shared_secret(&alice.public));
+        assert_eq!(alice_key, bob_key);
+
+        let message = b"Diffie-Hellman says hello";
+        let sent = encrypt_message(&alice_key, message)?;
+        assert_eq!(decrypt_message(&bob_key, &sent)?, message);
+
+        // Anyone else's key gets garbage or a padding error
+        let eve = group.generate_keypair();
+        let eve_key = derive_aes_key(&eve.shared_secret(&alice.public));
+        assert!(decrypt_message(&eve_key, &sent).map_or(true, |m| m != message));
+        Ok(())
+    }
+
+    #[test]
+    fn decrypt_message_rejects_partial_block() -> Result<()> {
+        let key = [0; 16];
+        let mut sent = encrypt_message(&key, b"sixteen byte msg")?;
+        assert_eq!(sent.len(), 48);
+        sent.remove(0);
+        assert!(decrypt_message(&key, &sent).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn checked_group_bounds() {
+        let (p, g) = (BigUint::from(37), BigUint::from(5));
+        assert!(Group::checked(p.clone(), g.clone()).is_ok());
+        assert!(Group::checked(p.clone(), BigUint::one()).is_err());
+        assert!(Group::checked(p.clone(), p.clone()).is_err());
+        assert!(Group::checked(BigUint::from(2), BigUint::one()).is_err());
+
+        // Only p matters here, and a p this small would have had key generation panic
+        assert!(Group::with_checked_modulus(p.clone(), p).is_ok());
+        assert!(Group::with_checked_modulus(BigUint::from(2), g).is_err());
+    }
+
+    #[test]
+    fn derived_key_is_sha1_prefix() {
+        let secret = BigUint::from(0x0102030405u64);
+        let key = derive_aes_key(&secret);
+        assert_eq!(key[..], openssl::sha::sha1(&[1, 2, 3, 4, 5])[..16]);
+    }
+}
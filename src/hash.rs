@@ -0,0 +1,154 @@
+pub mod hmac;
+pub mod md4;
+mod merkle_damgard;
+pub mod sha1;
+
+use merkle_damgard::{state_from_digest, Endian};
+
+// Common interface over hash functions, enough to build HMAC and friends on top of
+pub trait Hash: Sized {
+    const BLOCK_SIZE: usize;
+    const OUTPUT_SIZE: usize;
+
+    fn new() -> Self;
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self) -> Vec<u8>;
+
+    fn digest(data: &[u8]) -> Vec<u8> {
+        let mut h = Self::new();
+        h.update(data);
+        h.finalize()
+    }
+}
+
+// Merkle-Damgard hashes whose digest is their whole final state, so hashing can pick up again from
+// a digest. That plus the padding is everything length extension needs, see `attacks::mac`.
+pub trait MerkleDamgard: Hash {
+    // The padding appended to a message of `len` bytes
+    fn md_padding(len: u64) -> Vec<u8>;
+
+    // `from_state` with the state behind `digest`. None if `digest` is the wrong size.
+    fn from_digest(digest: &[u8], length: u64) -> Option<Self>;
+}
+
+// H(key || message). Broken by length extension, see `attacks::mac`.
+pub fn prefix_mac<H: Hash>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut h = H::new();
+    h.update(key);
+    h.update(message);
+    h.finalize()
+}
+
+pub fn verify_prefix_mac<H: Hash>(key: &[u8], message: &[u8], mac: &[u8]) -> bool {
+    prefix_mac::<H>(key, message) == mac
+}
+
+impl Hash for openssl::sha::Sha1 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 20;
+
+    fn new() -> Self {
+        openssl::sha::Sha1::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        openssl::sha::Sha1::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.finish().to_vec()
+    }
+}
+
+impl Hash for openssl::sha::Sha256 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 32;
+
+    fn new() -> Self {
+        openssl::sha::Sha256::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        openssl::sha::Sha256::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.finish().to_vec()
+    }
+}
+
+impl Hash for sha1::Sha1 {
+    const BLOCK_SIZE: usize = sha1::BLOCK_SIZE;
+    const OUTPUT_SIZE: usize = sha1::OUTPUT_SIZE;
+
+    fn new() -> Self {
+        sha1::Sha1::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        sha1::Sha1::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        sha1::Sha1::finalize(self).to_vec()
+    }
+}
+
+impl Hash for md4::Md4 {
+    const BLOCK_SIZE: usize = md4::BLOCK_SIZE;
+    const OUTPUT_SIZE: usize = md4::OUTPUT_SIZE;
+
+    fn new() -> Self {
+        md4::Md4::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        md4::Md4::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        md4::Md4::finalize(self).to_vec()
+    }
+}
+
+impl MerkleDamgard for sha1::Sha1 {
+    fn md_padding(len: u64) -> Vec<u8> {
+        sha1::md_padding(len)
+    }
+
+    fn from_digest(digest: &[u8], length: u64) -> Option<Self> {
+        let state = state_from_digest(digest, Endian::Big)?;
+        Some(sha1::Sha1::from_state(state, length))
+    }
+}
+
+impl MerkleDamgard for md4::Md4 {
+    fn md_padding(len: u64) -> Vec<u8> {
+        md4::md_padding(len)
+    }
+
+    fn from_digest(digest: &[u8], length: u64) -> Option<Self> {
+        let state = state_from_digest(digest, Endian::Little)?;
+        Some(md4::Md4::from_state(state, length))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_trait_matches_openssl() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            <openssl::sha::Sha1 as Hash>::digest(data),
+            openssl::sha::sha1(data)
+        );
+        assert_eq!(
+            <openssl::sha::Sha256 as Hash>::digest(data),
+            openssl::sha::sha256(data)
+        );
+        assert_eq!(<sha1::Sha1 as Hash>::digest(data), openssl::sha::sha1(data));
+        assert_eq!(<md4::Md4 as Hash>::digest(data), md4::md4(data));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::hash::Hash;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+// HMAC as described in RFC 2104, over any `Hash`
+// https://www.rfc-editor.org/rfc/rfc2104
+pub struct Hmac<H: Hash> {
+    inner: H,
+    outer_key: Vec<u8>,
+}
+
+impl<H: Hash> Hmac<H> {
+    pub fn new(key: &[u8]) -> Self {
+        // Keys longer than a block get hashed down first, then everything is zero padded out to
+        // a full block
+        let mut key = if key.len() > H::BLOCK_SIZE {
+            H::digest(key)
+        } else {
+            key.to_vec()
+        };
+        key.resize(H::BLOCK_SIZE, 0);
+
+        let mut inner = H::new();
+        inner.update(&key.iter().map(|k| k ^ IPAD).collect::<Vec<u8>>());
+        Hmac {
+            inner,
+            outer_key: key.iter().map(|k| k ^ OPAD).collect(),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let mut outer = H::new();
+        outer.update(&self.outer_key);
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+
+    // One-shot HMAC of `data` under `key`
+    pub fn mac(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut hmac = Self::new(key);
+        hmac.update(data);
+        hmac.finalize()
+    }
+
+    pub fn verify(key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+        Self::mac(key, data) == tag
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hex_to_u8, u8_to_hex, Result};
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::PKey;
+    use openssl::sha::{Sha1, Sha256};
+    use openssl::sign::Signer;
+    use rand::Rng;
+
+    fn openssl_hmac(digest: MessageDigest, key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+        let key = PKey::hmac(key)?;
+        let mut signer = Signer::new(digest, &key)?;
+        signer.update(data)?;
+        Ok(signer.sign_to_vec()?)
+    }
+
+    fn rfc_keys_and_data() -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        Ok(vec![
+            (vec![0x0b; 20], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xaa; 20], vec![0xdd; 50]),
+            (
+                hex_to_u8("0102030405060708090a0b0c0d0e0f10111213141516171819")?,
+                vec![0xcd; 50],
+            ),
+            (vec![0x0c; 20], b"Test With Truncation".to_vec()),
+        ])
+    }
+
+    #[test]
+    fn hmac_sha1_rfc_2202() -> Result<()> {
+        let mut tests = rfc_keys_and_data()?;
+        tests.push((
+            vec![0xaa; 80],
+            b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+        ));
+        tests.push((
+            vec![0xaa; 80],
+            b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data".to_vec(),
+        ));
+        let expected = [
+            "b617318655057264e28bc0b6fb378c8ef146be00",
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+            "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+            "4c9007f4026250c6bc8414f9bf50c86c2d7235da",
+            "4c1a03424b55e07fe7f27be1d58bb9324a9a5a04",
+            "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+            "e8e99d0f45237d786d6bbaa7965c7808bbff1a91",
+        ];
+        for ((key, data), expected) in tests.iter().zip(expected) {
+            assert_eq!(u8_to_hex(&Hmac::<Sha1>::mac(key, data)), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn hmac_sha256_rfc_4231() -> Result<()> {
+        let mut tests = rfc_keys_and_data()?;
+        tests.push((
+            vec![0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+        ));
+        tests.push((
+            vec![0xaa; 131],
+            b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.".to_vec(),
+        ));
+        let expected = [
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+            // Test case 5 only checks the first 128 bits
+            "a3b6167473100ee06e0c796c2955552b",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+        ];
+        for ((key, data), expected) in tests.iter().zip(expected) {
+            let mac = u8_to_hex(&Hmac::<Sha256>::mac(key, data));
+            assert_eq!(&mac[..expected.len()], expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn compare_openssl_hmac() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            // OpenSSL refuses an empty HMAC key, so start at one byte
+            let key: Vec<u8> = (0..rng.gen_range(1..150)).map(|_| rng.gen()).collect();
+            let data: Vec<u8> = (0..rng.gen_range(0..300)).map(|_| rng.gen()).collect();
+            assert_eq!(
+                Hmac::<Sha1>::mac(&key, &data),
+                openssl_hmac(MessageDigest::sha1(), &key, &data)?
+            );
+            assert_eq!(
+                Hmac::<Sha256>::mac(&key, &data),
+                openssl_hmac(MessageDigest::sha256(), &key, &data)?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn hmac_streaming() {
+        let key = b"YELLOW SUBMARINE";
+        let data = b"The quick brown fox jumps over the lazy dog".repeat(5);
+        let mut hmac = Hmac::<Sha256>::new(key);
+        for chunk in data.chunks(7) {
+            hmac.update(chunk);
+        }
+        let tag = hmac.finalize();
+        assert_eq!(tag, Hmac::<Sha256>::mac(key, &data));
+        assert!(Hmac::<Sha256>::verify(key, &data, &tag));
+        assert!(!Hmac::<Sha256>::verify(key, b"something else", &tag));
+
+        // Works over the hand written SHA-1 too
+        assert_eq!(
+            Hmac::<crate::hash::sha1::Sha1>::mac(key, &data),
+            Hmac::<Sha1>::mac(key, &data)
+        );
+    }
+}
@@ -0,0 +1,200 @@
+// MD4 as described in RFC 1320. Unlike SHA-1 it's little-endian throughout: the state words, the
+// digest and the length at the end of the padding.
+// https://www.rfc-editor.org/rfc/rfc1320
+
+use super::merkle_damgard::{self, Core, Endian};
+use super::{prefix_mac, verify_prefix_mac};
+
+pub const BLOCK_SIZE: usize = merkle_damgard::BLOCK_SIZE;
+pub const OUTPUT_SIZE: usize = 16;
+
+const INITIAL_STATE: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+#[derive(Clone)]
+pub struct Md4(Core<4>);
+
+impl Md4 {
+    pub fn new() -> Self {
+        Self::from_state(INITIAL_STATE, 0)
+    }
+
+    // See `merkle_damgard::Core::from_state`
+    pub fn from_state(state: [u32; 4], length: u64) -> Self {
+        Md4(Core::from_state(state, length, compress, Endian::Little))
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+
+    pub fn finalize(self) -> [u8; OUTPUT_SIZE] {
+        let mut digest = [0; OUTPUT_SIZE];
+        self.0.finalize(&mut digest);
+        digest
+    }
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn md4(data: &[u8]) -> [u8; OUTPUT_SIZE] {
+    let mut h = Md4::new();
+    h.update(data);
+    h.finalize()
+}
+
+// MD4(key || message)
+pub fn md4_prefix_mac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    prefix_mac::<Md4>(key, message)
+}
+
+pub fn verify_md4_prefix_mac(key: &[u8], message: &[u8], mac: &[u8]) -> bool {
+    verify_prefix_mac::<Md4>(key, message, mac)
+}
+
+// The padding MD4 appends to a message of `len` bytes, with the length little-endian
+pub fn md_padding(len: u64) -> Vec<u8> {
+    merkle_damgard::md_padding(len, Endian::Little)
+}
+
+fn f(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (!x & z)
+}
+
+fn g(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (x & z) | (y & z)
+}
+
+fn h(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
+
+// Round function, constant, message word order and shifts
+type Round = (fn(u32, u32, u32) -> u32, u32, [usize; 16], [u32; 4]);
+
+const ROUNDS: [Round; 3] = [
+    (
+        f,
+        0,
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+        [3, 7, 11, 19],
+    ),
+    (
+        g,
+        0x5a827999,
+        [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15],
+        [3, 5, 9, 13],
+    ),
+    (
+        h,
+        0x6ed9eba1,
+        [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15],
+        [3, 9, 11, 15],
+    ),
+];
+
+fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut x = [0u32; 16];
+    for (i, chunk) in block.chunks_exact(4).enumerate() {
+        x[i] = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let [mut a, mut b, mut c, mut d] = *state;
+    for (func, k, order, shifts) in ROUNDS {
+        for (i, &word) in order.iter().enumerate() {
+            let t = a
+                .wrapping_add(func(b, c, d))
+                .wrapping_add(x[word])
+                .wrapping_add(k)
+                .rotate_left(shifts[i % 4]);
+            a = d;
+            d = c;
+            c = b;
+            b = t;
+        }
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::MerkleDamgard;
+    use crate::u8_to_hex;
+
+    #[test]
+    fn md4_rfc_1320_vectors() {
+        let tests = [
+            ("", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+            ("a", "bde52cb31de33e46245e05fbdbd6fb24"),
+            ("abc", "a448017aaf21d8525fc10ae87aa6729d"),
+            ("message digest", "d9130a8164549fe818874806e1c7014b"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "d79e1c308aa5bbcdeea8ed63df412da9",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "043f8582f241db351ce627e153e7f0e4",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "e33b4ddc9c38f2199c3e7b164fcc0536",
+            ),
+        ];
+        for (test, expected) in &tests {
+            assert_eq!(u8_to_hex(&md4(test.as_bytes())), *expected);
+        }
+    }
+
+    #[test]
+    fn md4_streaming() {
+        let data =
+            b"12345678901234567890123456789012345678901234567890123456789012345678901234567890";
+        for size in 1..data.len() {
+            let mut h = Md4::new();
+            for chunk in data.chunks(size) {
+                h.update(chunk);
+            }
+            assert_eq!(h.finalize(), md4(data));
+        }
+    }
+
+    #[test]
+    fn md4_padding_little_endian() {
+        for len in 0..200u64 {
+            let padding = md_padding(len);
+            assert_eq!((len as usize + padding.len()) % BLOCK_SIZE, 0);
+            assert_eq!(padding[0], 0x80);
+            assert_eq!(&padding[padding.len() - 8..], &(len * 8).to_le_bytes());
+        }
+        // 0x0208 bits, which SHA-1 would write the other way round
+        assert_eq!(&md_padding(65)[55..], b"\x08\x02\0\0\0\0\0\0");
+    }
+
+    #[test]
+    fn md4_from_state() {
+        let message = b"YELLOW SUBMARINE".repeat(4);
+        let mut glued = message.clone();
+        glued.extend(md_padding(message.len() as u64));
+        glued.extend(b"and then some");
+
+        let mut h = Md4::from_digest(&md4(&message), 128).unwrap();
+        h.update(b"and then some");
+        assert_eq!(h.finalize(), md4(&glued));
+    }
+
+    #[test]
+    fn md4_prefix_mac_verify() {
+        let mac = md4_prefix_mac(b"YELLOW SUBMARINE", b"hello");
+        assert_eq!(mac, md4(b"YELLOW SUBMARINEhello"));
+        assert!(verify_md4_prefix_mac(b"YELLOW SUBMARINE", b"hello", &mac));
+        assert!(!verify_md4_prefix_mac(b"YELLOW SUBMARINE", b"hellO", &mac));
+    }
+}
@@ -0,0 +1,107 @@
+// The parts of SHA-1 and MD4 that don't depend on the compression function: buffering input into
+// 64-byte blocks, the padding and turning the state into a digest. The two only disagree on byte
+// order.
+
+pub const BLOCK_SIZE: usize = 64;
+
+// How the state words and the length at the end of the padding are written out
+#[derive(Clone, Copy)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+impl Endian {
+    fn u32_bytes(self, n: u32) -> [u8; 4] {
+        match self {
+            Endian::Big => n.to_be_bytes(),
+            Endian::Little => n.to_le_bytes(),
+        }
+    }
+
+    fn u32_from(self, bytes: [u8; 4]) -> u32 {
+        match self {
+            Endian::Big => u32::from_be_bytes(bytes),
+            Endian::Little => u32::from_le_bytes(bytes),
+        }
+    }
+
+    fn u64_bytes(self, n: u64) -> [u8; 8] {
+        match self {
+            Endian::Big => n.to_be_bytes(),
+            Endian::Little => n.to_le_bytes(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Core<const N: usize> {
+    state: [u32; N],
+    buffer: Vec<u8>,
+    // Total bytes fed in so far, including anything counted by `from_state`
+    length: u64,
+    compress: fn(&mut [u32; N], &[u8]),
+    endian: Endian,
+}
+
+impl<const N: usize> Core<N> {
+    // Carries on hashing from an arbitrary state, as if `length` bytes (padding included) had
+    // already been processed to reach it. `length` should be a multiple of the block size.
+    pub fn from_state(
+        state: [u32; N],
+        length: u64,
+        compress: fn(&mut [u32; N], &[u8]),
+        endian: Endian,
+    ) -> Self {
+        Core {
+            state,
+            buffer: Vec::with_capacity(BLOCK_SIZE),
+            length,
+            compress,
+            endian,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.buffer.extend_from_slice(data);
+
+        let full = self.buffer.len() - self.buffer.len() % BLOCK_SIZE;
+        for block in self.buffer[..full].chunks_exact(BLOCK_SIZE) {
+            (self.compress)(&mut self.state, block);
+        }
+        self.buffer.drain(..full);
+    }
+
+    // Pads out what's been fed in and writes the final state to `digest`, which is N words long
+    pub fn finalize(mut self, digest: &mut [u8]) {
+        let padding = md_padding(self.length, self.endian);
+        self.update(&padding);
+
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&self.endian.u32_bytes(word));
+        }
+    }
+}
+
+// Splits a digest back into the state words that produced it. None if it's the wrong size.
+pub fn state_from_digest<const N: usize>(digest: &[u8], endian: Endian) -> Option<[u32; N]> {
+    if digest.len() != N * 4 {
+        return None;
+    }
+    let mut state = [0; N];
+    for (word, chunk) in state.iter_mut().zip(digest.chunks_exact(4)) {
+        *word = endian.u32_from(chunk.try_into().unwrap());
+    }
+    Some(state)
+}
+
+// The padding for a message of `len` bytes: a 1 bit, zeroes up to 56 bytes into the block, then
+// the message length in bits as a u64
+pub fn md_padding(len: u64, endian: Endian) -> Vec<u8> {
+    let mut padding = vec![0x80];
+    let zeroes = (BLOCK_SIZE * 2 - 9 - (len as usize % BLOCK_SIZE)) % BLOCK_SIZE;
+    padding.extend(vec![0; zeroes]);
+    padding.extend(endian.u64_bytes(len.wrapping_mul(8)));
+    padding
+}
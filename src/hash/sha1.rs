@@ -0,0 +1,181 @@
+// SHA-1 as described in FIPS 180-4, written out by hand so the internal state can be poked at
+// https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+
+use super::merkle_damgard::{self, Core, Endian};
+use super::{prefix_mac, verify_prefix_mac};
+
+pub const BLOCK_SIZE: usize = merkle_damgard::BLOCK_SIZE;
+pub const OUTPUT_SIZE: usize = 20;
+
+const INITIAL_STATE: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+#[derive(Clone)]
+pub struct Sha1(Core<5>);
+
+impl Sha1 {
+    pub fn new() -> Self {
+        Self::from_state(INITIAL_STATE, 0)
+    }
+
+    // See `merkle_damgard::Core::from_state`
+    pub fn from_state(state: [u32; 5], length: u64) -> Self {
+        Sha1(Core::from_state(state, length, compress, Endian::Big))
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+
+    pub fn finalize(self) -> [u8; OUTPUT_SIZE] {
+        let mut digest = [0; OUTPUT_SIZE];
+        self.0.finalize(&mut digest);
+        digest
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn sha1(data: &[u8]) -> [u8; OUTPUT_SIZE] {
+    let mut h = Sha1::new();
+    h.update(data);
+    h.finalize()
+}
+
+// SHA1(key || message)
+pub fn sha1_prefix_mac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    prefix_mac::<Sha1>(key, message)
+}
+
+pub fn verify_sha1_prefix_mac(key: &[u8], message: &[u8], mac: &[u8]) -> bool {
+    verify_prefix_mac::<Sha1>(key, message, mac)
+}
+
+// The padding SHA-1 appends to a message of `len` bytes, with the length big-endian
+pub fn md_padding(len: u64) -> Vec<u8> {
+    merkle_damgard::md_padding(len, Endian::Big)
+}
+
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+    let mut w = [0u32; 80];
+    for (i, chunk) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    for (i, &wi) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5a827999),
+            20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(wi);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::MerkleDamgard;
+    use crate::u8_to_hex;
+    use rand::Rng;
+
+    #[test]
+    fn sha1_fips_180_vectors() {
+        let tests = [
+            ("", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            ("abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            ),
+            (
+                "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+                "a49b2446a02c645bf419f995b67091253a04a259",
+            ),
+        ];
+        for (test, expected) in &tests {
+            assert_eq!(u8_to_hex(&sha1(test.as_bytes())), *expected);
+        }
+
+        let mut h = Sha1::new();
+        for _ in 0..1000 {
+            h.update(&[b'a'; 1000]);
+        }
+        assert_eq!(
+            u8_to_hex(&h.finalize()),
+            "34aa973cd4c4daa4f61eeb2bdbad27316534016f"
+        );
+    }
+
+    #[test]
+    fn compare_openssl_sha1() {
+        let mut rng = rand::thread_rng();
+        for len in 0..300 {
+            let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            assert_eq!(sha1(&data), openssl::sha::sha1(&data));
+
+            // Same again fed in uneven pieces
+            let mut h = Sha1::new();
+            for chunk in data.chunks(rng.gen_range(1..=70)) {
+                h.update(chunk);
+            }
+            assert_eq!(h.finalize(), openssl::sha::sha1(&data));
+        }
+    }
+
+    #[test]
+    fn sha1_padding() {
+        for len in 0..200u64 {
+            let padding = md_padding(len);
+            assert_eq!((len as usize + padding.len()) % BLOCK_SIZE, 0);
+            assert_eq!(padding[0], 0x80);
+            assert_eq!(&padding[padding.len() - 8..], &(len * 8).to_be_bytes());
+        }
+        assert_eq!(md_padding(55).len(), 9);
+        assert_eq!(md_padding(56).len(), 72);
+    }
+
+    #[test]
+    fn sha1_prefix_mac_verify() {
+        let mac = sha1_prefix_mac(b"YELLOW SUBMARINE", b"hello");
+        assert_eq!(mac, sha1(b"YELLOW SUBMARINEhello"));
+        assert!(verify_sha1_prefix_mac(b"YELLOW SUBMARINE", b"hello", &mac));
+        assert!(!verify_sha1_prefix_mac(b"YELLOW SUBMARINE", b"hellO", &mac));
+        assert!(!verify_sha1_prefix_mac(b"YELLOW SUBMARINf", b"hello", &mac));
+    }
+
+    #[test]
+    fn sha1_from_state() {
+        // Hashing a block and then resuming from its state is the same as hashing straight through
+        let message = b"YELLOW SUBMARINE".repeat(4);
+        let mut glued = message.clone();
+        glued.extend(md_padding(message.len() as u64));
+        glued.extend(b"and then some");
+
+        let mut h = Sha1::from_digest(&sha1(&message), 128).unwrap();
+        h.update(b"and then some");
+        assert_eq!(h.finalize(), sha1(&glued));
+    }
+}
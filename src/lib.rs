@@ -1,5 +1,14 @@
+pub mod attacks;
 pub mod base64;
+pub mod bignum;
 pub mod cipher;
+pub mod dh;
+pub mod hash;
+pub mod profile;
+pub mod protocol;
+pub mod rng;
+pub mod server;
+pub mod srp;
 
 use bitvec::prelude::*;
 use std::error::Error;
@@ -0,0 +1,88 @@
+use crate::cipher::{decrypt, encrypt, CipherMode};
+use crate::Result;
+use std::str;
+
+// Parses a structured cookie of the form `foo=bar&baz=qux&zap=zazzle`, keeping the order in which
+// the keys appear
+pub fn parse_kv(input: &str) -> Result<Vec<(String, String)>> {
+    input
+        .split('&')
+        .map(|pair| {
+            let (k, v) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("error: malformed pair '{}'", pair))?;
+            Ok((k.to_owned(), v.to_owned()))
+        })
+        .collect()
+}
+
+pub fn encode_kv(pairs: &[(String, String)]) -> String {
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+// Encodes a user profile for the given email. Metacharacters are eaten so the email can't add
+// keys of its own.
+pub fn profile_for(email: &str) -> String {
+    let email: String = email.chars().filter(|&c| c != '&' && c != '=').collect();
+    encode_kv(&[
+        ("email".to_owned(), email),
+        ("uid".to_owned(), "10".to_owned()),
+        ("role".to_owned(), "user".to_owned()),
+    ])
+}
+
+pub fn encrypt_profile(email: &str, key: &[u8]) -> Result<Vec<u8>> {
+    encrypt(CipherMode::ECB, profile_for(email).as_bytes(), key, None)
+}
+
+pub fn decrypt_profile(input: &[u8], key: &[u8]) -> Result<Vec<(String, String)>> {
+    let decrypted = decrypt(CipherMode::ECB, input, key, None)?;
+    parse_kv(str::from_utf8(&decrypted)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::gen_aes_128_key;
+
+    #[test]
+    fn parse_encode_kv() -> Result<()> {
+        let cookie = "foo=bar&baz=qux&zap=zazzle";
+        let parsed = parse_kv(cookie)?;
+        assert_eq!(
+            parsed,
+            vec![
+                ("foo".to_owned(), "bar".to_owned()),
+                ("baz".to_owned(), "qux".to_owned()),
+                ("zap".to_owned(), "zazzle".to_owned()),
+            ]
+        );
+        assert_eq!(encode_kv(&parsed), cookie);
+        assert!(parse_kv("foo=bar&baz").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn profile_for_strips_metacharacters() {
+        assert_eq!(
+            profile_for("foo@bar.com"),
+            "email=foo@bar.com&uid=10&role=user"
+        );
+        assert_eq!(
+            profile_for("foo@bar.com&role=admin"),
+            "email=foo@bar.comroleadmin&uid=10&role=user"
+        );
+    }
+
+    #[test]
+    fn encrypt_decrypt_profile() -> Result<()> {
+        let key = gen_aes_128_key();
+        let profile = decrypt_profile(&encrypt_profile("foo@bar.com", &key)?, &key)?;
+        assert_eq!(profile, parse_kv(&profile_for("foo@bar.com"))?);
+        Ok(())
+    }
+}
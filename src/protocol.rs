@@ -0,0 +1,278 @@
+pub mod dh_echo;
+pub mod dh_negotiated;
+
+use crate::bignum::BigUint;
+use crate::dh::{decrypt_message, derive_aes_key, encrypt_message, Group};
+use crate::Result;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+// How long a party waits on a message before giving up, so one the middle dropped shows up as an
+// error rather than a hang
+const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Which way a message is headed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    AToB,
+    BToA,
+}
+
+enum Event<M> {
+    Message(Direction, M),
+    Hangup(Direction),
+}
+
+// A party's end of the line. Everything sent goes to the middle, which decides what reaches the
+// other side.
+pub struct Endpoint<M> {
+    outgoing: Direction,
+    tx: Sender<Event<M>>,
+    rx: Receiver<M>,
+}
+
+impl<M> Endpoint<M> {
+    pub fn send(&self, message: M) -> Result<()> {
+        self.tx
+            .send(Event::Message(self.outgoing, message))
+            .map_err(|_| "error: the line is down".into())
+    }
+
+    // None once the other side has hung up and everything it sent has been read
+    pub fn recv(&self) -> Result<Option<M>> {
+        match self.rx.recv_timeout(RECV_TIMEOUT) {
+            Ok(message) => Ok(Some(message)),
+            Err(RecvTimeoutError::Disconnected) => Ok(None),
+            Err(RecvTimeoutError::Timeout) => Err("error: timed out waiting for a message".into()),
+        }
+    }
+
+    // For protocol steps where the other side hanging up is an error
+    pub fn expect(&self) -> Result<M> {
+        self.recv()?
+            .ok_or_else(|| "error: the other side hung up".into())
+    }
+}
+
+impl<M> Drop for Endpoint<M> {
+    fn drop(&mut self) {
+        // Lets the middle disconnect the other side. It listens for as long as any endpoint is
+        // alive, so this always gets through.
+        let _ = self.tx.send(Event::Hangup(self.outgoing));
+    }
+}
+
+// One side of a protocol, run on its own thread until it hangs up
+pub trait Party<M>: Send {
+    type Output: Send;
+
+    fn run(self, endpoint: Endpoint<M>) -> Result<Self::Output>;
+}
+
+// Sits on the line between the parties and sees every message
+pub trait Middle<M> {
+    // Returns what to deliver in place of `message`, or None to drop it
+    fn intercept(&mut self, direction: Direction, message: M) -> Option<M>;
+}
+
+// A protocol message type with a variant for encrypted data, which is ciphertext with the IV
+// appended, see `dh::encrypt_message`
+pub trait DataMessage: Sized {
+    fn data(data: Vec<u8>) -> Self;
+
+    // None for any other kind of message
+    fn into_data(self) -> Option<Vec<u8>>;
+}
+
+// The Diffie-Hellman steps every protocol here shares: makes a keypair in `group`, hands our
+// public key to `exchange` to get the other side's back, and derives the AES key from the result
+pub fn exchange_keys(
+    group: &Group,
+    exchange: impl FnOnce(&BigUint) -> Result<BigUint>,
+) -> Result<[u8; 16]> {
+    let keys = group.generate_keypair();
+    let public = exchange(&keys.public)?;
+    Ok(derive_aes_key(&keys.shared_secret(&public)))
+}
+
+// A's side of an echo: sends each message in turn and returns the echoes
+pub fn send_for_echoes<M: DataMessage>(
+    endpoint: &Endpoint<M>,
+    key: &[u8; 16],
+    messages: &[Vec<u8>],
+) -> Result<Vec<Vec<u8>>> {
+    let mut echoes = vec![];
+    for message in messages {
+        endpoint.send(M::data(encrypt_message(key, message)?))?;
+        let Some(data) = endpoint.expect()?.into_data() else {
+            return Err("error: expected an echo".into());
+        };
+        echoes.push(decrypt_message(key, &data)?);
+    }
+    Ok(echoes)
+}
+
+// B's side of an echo: sends every message back until the other side hangs up, and returns what
+// it received
+pub fn echo_until_hangup<M: DataMessage>(
+    endpoint: &Endpoint<M>,
+    key: &[u8; 16],
+) -> Result<Vec<Vec<u8>>> {
+    let mut received = vec![];
+    while let Some(message) = endpoint.recv()? {
+        let Some(data) = message.into_data() else {
+            return Err("error: expected a message to echo".into());
+        };
+        let plaintext = decrypt_message(key, &data)?;
+        endpoint.send(M::data(encrypt_message(key, &plaintext)?))?;
+        received.push(plaintext);
+    }
+    Ok(received)
+}
+
+// Delivers everything untouched
+pub struct Wire;
+
+impl<M> Middle<M> for Wire {
+    fn intercept(&mut self, _: Direction, message: M) -> Option<M> {
+        Some(message)
+    }
+}
+
+// What came out of a run: each party's output and every message as it was delivered
+pub struct Session<A, B, M> {
+    pub a: A,
+    pub b: B,
+    pub transcript: Vec<(Direction, M)>,
+}
+
+// Runs `a` and `b` on their own threads, relaying every message between them through `middle` on
+// this one. When either side hangs up the other sees it on its next `recv`.
+pub fn run<M, A, B>(
+    a: A,
+    b: B,
+    middle: &mut impl Middle<M>,
+) -> Result<Session<A::Output, B::Output, M>>
+where
+    M: Clone + Send,
+    A: Party<M>,
+    B: Party<M>,
+{
+    let (tx, events) = mpsc::channel();
+    let (to_a, a_rx) = mpsc::channel();
+    let (to_b, b_rx) = mpsc::channel();
+    let a_end = Endpoint {
+        outgoing: Direction::AToB,
+        tx: tx.clone(),
+        rx: a_rx,
+    };
+    let b_end = Endpoint {
+        outgoing: Direction::BToA,
+        tx,
+        rx: b_rx,
+    };
+
+    thread::scope(|s| {
+        // Errors are stringified to get them across the thread boundary
+        let a = s.spawn(move || a.run(a_end).map_err(|e| e.to_string()));
+        let b = s.spawn(move || b.run(b_end).map_err(|e| e.to_string()));
+
+        let (mut to_a, mut to_b) = (Some(to_a), Some(to_b));
+        let mut transcript = vec![];
+        // Ends once both endpoints are dropped
+        for event in events {
+            match event {
+                Event::Message(direction, message) => {
+                    let Some(message) = middle.intercept(direction, message) else {
+                        continue;
+                    };
+                    transcript.push((direction, message.clone()));
+                    let to = match direction {
+                        Direction::AToB => &to_b,
+                        Direction::BToA => &to_a,
+                    };
+                    if let Some(to) = to {
+                        // The receiver may have already finished, which is its business
+                        let _ = to.send(message);
+                    }
+                }
+                Event::Hangup(Direction::AToB) => to_b = None,
+                Event::Hangup(Direction::BToA) => to_a = None,
+            }
+        }
+
+        Ok(Session {
+            a: a.join().map_err(|_| "error: party A panicked")??,
+            b: b.join().map_err(|_| "error: party B panicked")??,
+            transcript,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter(u32);
+
+    impl Party<u32> for Counter {
+        type Output = ();
+
+        fn run(self, endpoint: Endpoint<u32>) -> Result<()> {
+            for i in 1..=self.0 {
+                endpoint.send(i)?;
+            }
+            Ok(())
+        }
+    }
+
+    struct Collector;
+
+    impl Party<u32> for Collector {
+        type Output = Vec<u32>;
+
+        fn run(self, endpoint: Endpoint<u32>) -> Result<Vec<u32>> {
+            let mut received = vec![];
+            while let Some(n) = endpoint.recv()? {
+                received.push(n);
+            }
+            Ok(received)
+        }
+    }
+
+    // Drops odd numbers and doubles even ones, keeping a note of everything it saw
+    #[derive(Default)]
+    struct Meddler {
+        seen: Vec<u32>,
+    }
+
+    impl Middle<u32> for Meddler {
+        fn intercept(&mut self, direction: Direction, n: u32) -> Option<u32> {
+            assert_eq!(direction, Direction::AToB);
+            self.seen.push(n);
+            n.is_multiple_of(2).then_some(n * 2)
+        }
+    }
+
+    #[test]
+    fn wire_delivers_everything() -> Result<()> {
+        let session = run(Counter(5), Collector, &mut Wire)?;
+        assert_eq!(session.b, vec![1, 2, 3, 4, 5]);
+        assert_eq!(session.transcript.len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn middle_rewrites_and_drops() -> Result<()> {
+        let mut middle = Meddler::default();
+        let session = run(Counter(5), Collector, &mut middle)?;
+        assert_eq!(middle.seen, vec![1, 2, 3, 4, 5]);
+        assert_eq!(session.b, vec![4, 8]);
+        assert_eq!(
+            session.transcript,
+            vec![(Direction::AToB, 4), (Direction::AToB, 8)]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use crate::bignum::BigUint;
+use crate::dh::Group;
+use crate::protocol::{
+    echo_until_hangup, exchange_keys, send_for_echoes, DataMessage, Endpoint, Party,
+};
+use crate::Result;
+
+// The challenge 34 echo protocol: A opens with the group and its public key, B answers with its
+// own, then every message A sends is echoed back by B, each under the key from the shared secret
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EchoMessage {
+    Hello {
+        p: BigUint,
+        g: BigUint,
+        public: BigUint,
+    },
+    Reply {
+        public: BigUint,
+    },
+    Data(Vec<u8>),
+}
+
+impl DataMessage for EchoMessage {
+    fn data(data: Vec<u8>) -> Self {
+        EchoMessage::Data(data)
+    }
+
+    fn into_data(self) -> Option<Vec<u8>> {
+        match self {
+            EchoMessage::Data(data) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+// A: sends each message in turn and collects the echoes
+pub struct EchoClient {
+    group: Group,
+    messages: Vec<Vec<u8>>,
+}
+
+impl EchoClient {
+    pub fn new(group: Group, messages: Vec<Vec<u8>>) -> Self {
+        EchoClient { group, messages }
+    }
+}
+
+impl Party<EchoMessage> for EchoClient {
+    type Output = Vec<Vec<u8>>;
+
+    fn run(self, endpoint: Endpoint<EchoMessage>) -> Result<Vec<Vec<u8>>> {
+        let key = exchange_keys(&self.group, |public| {
+            endpoint.send(EchoMessage::Hello {
+                p: self.group.p.clone(),
+                g: self.group.g.clone(),
+                public: public.clone(),
+            })?;
+            let EchoMessage::Reply { public } = endpoint.expect()? else {
+                return Err("error: expected the other side's public key".into());
+            };
+            Ok(public)
+        })?;
+        send_for_echoes(&endpoint, &key, &self.messages)
+    }
+}
+
+// B: takes the group A offers, provided it's a usable one, and echoes every message until A hangs
+// up. Returns what it received.
+pub struct EchoBot;
+
+impl Party<EchoMessage> for EchoBot {
+    type Output = Vec<Vec<u8>>;
+
+    fn run(self, endpoint: Endpoint<EchoMessage>) -> Result<Vec<Vec<u8>>> {
+        let EchoMessage::Hello { p, g, public } = endpoint.expect()? else {
+            return Err("error: expected a hello".into());
+        };
+        let key = exchange_keys(&Group::checked(p, g)?, |own| {
+            endpoint.send(EchoMessage::Reply {
+                public: own.clone(),
+            })?;
+            Ok(public)
+        })?;
+        echo_until_hangup(&endpoint, &key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{run, Direction, Wire};
+
+    #[test]
+    fn echo_over_wire() -> Result<()> {
+        let messages: Vec<Vec<u8>> = vec![b"hello".to_vec(), b"is it me".to_vec()];
+        let client = EchoClient::new(Group::modp_1536(), messages.clone());
+        let session = run(client, EchoBot, &mut Wire)?;
+
+        assert_eq!(session.a, messages);
+        assert_eq!(session.b, messages);
+        // Hello, reply, then a message and its echo for each
+        assert_eq!(session.transcript.len(), 2 + 2 * messages.len());
+        assert_eq!(session.transcript[1].0, Direction::BToA);
+        Ok(())
+    }
+}
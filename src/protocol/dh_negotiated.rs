@@ -0,0 +1,117 @@
+use crate::bignum::BigUint;
+use crate::dh::Group;
+use crate::protocol::{
+    echo_until_hangup, exchange_keys, send_for_echoes, DataMessage, Endpoint, Party,
+};
+use crate::Result;
+
+// The challenge 35 protocol: the echo protocol again, except A first proposes a group and B
+// acknowledges the one it's going to use before any public keys change hands. A takes B's word
+// for it, and nobody checks that g is a sensible generator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NegotiatedMessage {
+    Params { p: BigUint, g: BigUint },
+    Ack { p: BigUint, g: BigUint },
+    PublicKey(BigUint),
+    Data(Vec<u8>),
+}
+
+impl DataMessage for NegotiatedMessage {
+    fn data(data: Vec<u8>) -> Self {
+        NegotiatedMessage::Data(data)
+    }
+
+    fn into_data(self) -> Option<Vec<u8>> {
+        match self {
+            NegotiatedMessage::Data(data) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+// A: negotiates the group, then sends each message in turn and collects the echoes
+pub struct NegotiatingClient {
+    group: Group,
+    messages: Vec<Vec<u8>>,
+}
+
+impl NegotiatingClient {
+    pub fn new(group: Group, messages: Vec<Vec<u8>>) -> Self {
+        NegotiatingClient { group, messages }
+    }
+}
+
+impl Party<NegotiatedMessage> for NegotiatingClient {
+    type Output = Vec<Vec<u8>>;
+
+    fn run(self, endpoint: Endpoint<NegotiatedMessage>) -> Result<Vec<Vec<u8>>> {
+        endpoint.send(NegotiatedMessage::Params {
+            p: self.group.p.clone(),
+            g: self.group.g.clone(),
+        })?;
+        let NegotiatedMessage::Ack { p, g } = endpoint.expect()? else {
+            return Err("error: expected the group to be acknowledged".into());
+        };
+
+        // g is taken on trust, which is the hole challenge 35 goes through
+        let key = exchange_keys(&Group::with_checked_modulus(p, g)?, |public| {
+            endpoint.send(NegotiatedMessage::PublicKey(public.clone()))?;
+            let NegotiatedMessage::PublicKey(public) = endpoint.expect()? else {
+                return Err("error: expected the other side's public key".into());
+            };
+            Ok(public)
+        })?;
+        send_for_echoes(&endpoint, &key, &self.messages)
+    }
+}
+
+// B: accepts whatever group is proposed and echoes every message until A hangs up. Returns what
+// it received.
+pub struct NegotiatingBot;
+
+impl Party<NegotiatedMessage> for NegotiatingBot {
+    type Output = Vec<Vec<u8>>;
+
+    fn run(self, endpoint: Endpoint<NegotiatedMessage>) -> Result<Vec<Vec<u8>>> {
+        let NegotiatedMessage::Params { p, g } = endpoint.expect()? else {
+            return Err("error: expected a group".into());
+        };
+        endpoint.send(NegotiatedMessage::Ack {
+            p: p.clone(),
+            g: g.clone(),
+        })?;
+
+        let key = exchange_keys(&Group::with_checked_modulus(p, g)?, |own| {
+            let NegotiatedMessage::PublicKey(public) = endpoint.expect()? else {
+                return Err("error: expected the other side's public key".into());
+            };
+            endpoint.send(NegotiatedMessage::PublicKey(own.clone()))?;
+            Ok(public)
+        })?;
+        echo_until_hangup(&endpoint, &key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{run, Wire};
+
+    #[test]
+    fn negotiated_echo_over_wire() -> Result<()> {
+        let messages: Vec<Vec<u8>> = vec![b"proposing".to_vec(), b"a group".to_vec()];
+        let client = NegotiatingClient::new(Group::modp_1536(), messages.clone());
+        let session = run(client, NegotiatingBot, &mut Wire)?;
+
+        assert_eq!(session.a, messages);
+        assert_eq!(session.b, messages);
+        assert_eq!(
+            session.transcript[1].1,
+            NegotiatedMessage::Ack {
+                p: Group::modp_1536().p,
+                g: BigUint::from(2)
+            }
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+use rand::{Error, RngCore};
+use std::cell::Cell;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const N: usize = 624;
+const M: usize = 397;
+const MATRIX_A: u32 = 0x9908b0df;
+const UPPER_MASK: u32 = 0x80000000;
+const LOWER_MASK: u32 = 0x7fffffff;
+
+// 32-bit MT19937 Mersenne Twister
+// https://en.wikipedia.org/wiki/Mersenne_Twister
+#[derive(Clone)]
+pub struct Mt19937 {
+    state: [u32; N],
+    index: usize,
+}
+
+impl Mt19937 {
+    pub fn new(seed: u32) -> Self {
+        let mut state = [0; N];
+        state[0] = seed;
+        for i in 1..N {
+            state[i] = 1812433253u32
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+        // Twist on the first call
+        Mt19937 { state, index: N }
+    }
+
+    // Picks up from a raw (untempered) state, as if the generator had just twisted
+    pub fn from_state(state: [u32; N]) -> Self {
+        Mt19937 { state, index: N }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        if self.index >= N {
+            self.twist();
+        }
+        let y = self.state[self.index];
+        self.index += 1;
+        temper(y)
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N {
+            let y = (self.state[i] & UPPER_MASK) | (self.state[(i + 1) % N] & LOWER_MASK);
+            let mut next = y >> 1;
+            if y & 1 != 0 {
+                next ^= MATRIX_A;
+            }
+            self.state[i] = self.state[(i + M) % N] ^ next;
+        }
+        self.index = 0;
+    }
+}
+
+impl RngCore for Mt19937 {
+    fn next_u32(&mut self) -> u32 {
+        Mt19937::next_u32(self)
+    }
+
+    // Low word first, same as rand_core's helpers
+    fn next_u64(&mut self) -> u64 {
+        let low = Mt19937::next_u32(self) as u64;
+        let high = Mt19937::next_u32(self) as u64;
+        (high << 32) | low
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = Mt19937::next_u32(self).to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+pub fn temper(mut y: u32) -> u32 {
+    y ^= y >> 11;
+    y ^= (y << 7) & 0x9d2c5680;
+    y ^= (y << 15) & 0xefc60000;
+    y ^= y >> 18;
+    y
+}
+
+// Inverts `temper` one step at a time, last step first
+pub fn untemper(mut y: u32) -> u32 {
+    y = undo_right_shift(y, 18);
+    y = undo_left_shift(y, 15, 0xefc60000);
+    y = undo_left_shift(y, 7, 0x9d2c5680);
+    undo_right_shift(y, 11)
+}
+
+// y ^= y >> shift: the top `shift` bits come through untouched, and each pass recovers the next
+// `shift` bits below them
+fn undo_right_shift(y: u32, shift: u32) -> u32 {
+    let mut x = y;
+    for _ in 0..32 / shift {
+        x = y ^ (x >> shift);
+    }
+    x
+}
+
+// y ^= (y << shift) & mask: same as above, working up from the bottom bits
+fn undo_left_shift(y: u32, shift: u32, mask: u32) -> u32 {
+    let mut x = y;
+    for _ in 0..32 / shift {
+        x = y ^ ((x << shift) & mask);
+    }
+    x
+}
+
+// Rebuilds a generator from 624 consecutive outputs. Untempering each output gives back the
+// internal state it came from, and the clone continues exactly where the original left off.
+pub fn clone_from_outputs(outputs: &[u32; N]) -> Mt19937 {
+    let mut state = [0; N];
+    for (s, &o) in state.iter_mut().zip(outputs) {
+        *s = untemper(o);
+    }
+    Mt19937::from_state(state)
+}
+
+// Stream cipher keyed by a 16-bit seed: the keystream is successive MT19937 outputs, four bytes
+// apiece. Encrypting and decrypting are the same operation.
+pub fn mt19937_cipher(input: &[u8], seed: u16) -> Vec<u8> {
+    let mut keystream = vec![0; input.len()];
+    Mt19937::new(seed as u32).fill_bytes(&mut keystream);
+    input.iter().zip(keystream).map(|(i, k)| i ^ k).collect()
+}
+
+// Where time comes from, so anything that seeds from or waits on the clock can be run against a fake
+// one in tests
+pub trait Clock {
+    // Seconds since the UNIX epoch
+    fn now(&self) -> u64;
+    fn sleep(&self, secs: u64);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    fn sleep(&self, secs: u64) {
+        std::thread::sleep(Duration::from_secs(secs));
+    }
+}
+
+// Starts at a given time and only moves when slept on, which it does instantly
+pub struct FakeClock {
+    now: Cell<u64>,
+}
+
+impl FakeClock {
+    pub fn new(start: u64) -> Self {
+        FakeClock {
+            now: Cell::new(start),
+        }
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> u64 {
+        self.now.get()
+    }
+
+    fn sleep(&self, secs: u64) {
+        self.now.set(self.now.get() + secs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn mt19937_reference_output() {
+        let mut mt = Mt19937::new(5489);
+        let first: Vec<u32> = (0..5).map(|_| mt.next_u32()).collect();
+        assert_eq!(
+            first,
+            [3499211612, 581869302, 3890346734, 3586334585, 545404204]
+        );
+
+        // std::mt19937 is required to produce this as its 10000th value
+        let mut mt = Mt19937::new(5489);
+        let last = (0..10000).map(|_| mt.next_u32()).last();
+        assert_eq!(last, Some(4123659995));
+    }
+
+    #[test]
+    fn untemper_inverts_temper() {
+        let mut rng = rand::thread_rng();
+        for y in [0, 1, u32::MAX, 0x80000000, 0xdeadbeef] {
+            assert_eq!(untemper(temper(y)), y);
+        }
+        for _ in 0..1000 {
+            let y: u32 = rng.gen();
+            assert_eq!(untemper(temper(y)), y);
+        }
+    }
+
+    #[test]
+    fn clone_predicts_outputs() {
+        let mut mt = Mt19937::new(rand::thread_rng().gen());
+        // Burn a few outputs so the clone doesn't start on a twist boundary
+        for _ in 0..100 {
+            mt.next_u32();
+        }
+
+        let mut outputs = [0; N];
+        for o in outputs.iter_mut() {
+            *o = mt.next_u32();
+        }
+        let mut clone = clone_from_outputs(&outputs);
+        for _ in 0..1000 {
+            assert_eq!(clone.next_u32(), mt.next_u32());
+        }
+    }
+
+    #[test]
+    fn mt19937_cipher_roundtrip() {
+        let message = b"The quick brown fox jumps over the lazy dog";
+        let ciphertext = mt19937_cipher(message, 0xbeef);
+        assert_ne!(&ciphertext[..], &message[..]);
+        assert_eq!(mt19937_cipher(&ciphertext, 0xbeef), message);
+        assert_ne!(mt19937_cipher(&ciphertext, 0xbeee), message);
+    }
+
+    #[test]
+    fn fake_clock_sleeps_instantly() {
+        let clock = FakeClock::new(1_000_000);
+        clock.sleep(500);
+        assert_eq!(clock.now(), 1_000_500);
+    }
+
+    #[test]
+    fn mt19937_as_rng() {
+        let mut a = Mt19937::new(1);
+        let mut b = Mt19937::new(1);
+        let mut buf = [0; 16];
+        a.fill(&mut buf);
+        assert_ne!(buf, [0; 16]);
+        assert_eq!(&buf[..4], &b.next_u32().to_le_bytes());
+        assert!((5..=10).contains(&a.gen_range(5..=10)));
+    }
+}
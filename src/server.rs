@@ -0,0 +1,147 @@
+use crate::hash::hmac::Hmac;
+use crate::{hex_to_u8, Result};
+use openssl::sha::Sha1;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// Compares byte by byte, sleeping after every byte that matches and bailing out at the first one
+// that doesn't, so the time taken gives away how much of `a` was right
+pub fn insecure_compare(a: &[u8], b: &[u8], delay: Duration) -> bool {
+    for (x, y) in a.iter().zip(b) {
+        if x != y {
+            return false;
+        }
+        thread::sleep(delay);
+    }
+    a.len() == b.len()
+}
+
+// Serves forever, handing each connection to `handler` on a thread of its own
+pub fn serve<F>(listener: TcpListener, handler: F) -> Result<()>
+where
+    F: Fn(TcpStream) -> Result<()> + Send + Sync + 'static,
+{
+    let handler = Arc::new(handler);
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let handler = Arc::clone(&handler);
+        // A dropped connection only matters to that client
+        thread::spawn(move || handler(stream).ok());
+    }
+    Ok(())
+}
+
+// Binds to a random localhost port and serves from a background thread
+pub fn spawn<F>(handler: F) -> Result<SocketAddr>
+where
+    F: Fn(TcpStream) -> Result<()> + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    thread::spawn(move || serve(listener, handler).ok());
+    Ok(addr)
+}
+
+// Handles `GET /test?file=foo&signature=...`, answering 200 if the signature is the HMAC-SHA1 of
+// the file name under `key` and 500 otherwise. The signature is checked with `insecure_compare`.
+pub fn hmac_handler(
+    key: Vec<u8>,
+    delay: Duration,
+) -> impl Fn(TcpStream) -> Result<()> + Send + Sync + 'static {
+    move |stream| handle(stream, &key, delay)
+}
+
+fn handle(mut stream: TcpStream, key: &[u8], delay: Duration) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+
+    let status = match check_request(&request_line, key, delay) {
+        Some(true) => "200 OK",
+        Some(false) => "500 Internal Server Error",
+        None => "400 Bad Request",
+    };
+    // One write, so Nagle doesn't hold the tail of the response back and skew the client's timing
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    );
+    stream.write_all(response.as_bytes())?;
+    Ok(())
+}
+
+// None if the request isn't one we understand
+fn check_request(request_line: &str, key: &[u8], delay: Duration) -> Option<bool> {
+    let mut parts = request_line.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    let (path, query) = parts.next()?.split_once('?')?;
+    if path != "/test" {
+        return None;
+    }
+
+    let mut file = None;
+    let mut signature = None;
+    for pair in query.split('&') {
+        match pair.split_once('=')? {
+            ("file", v) => file = Some(v),
+            ("signature", v) => signature = Some(v),
+            _ => {}
+        }
+    }
+    let signature = signature?;
+    if !signature.is_ascii() || !signature.len().is_multiple_of(2) {
+        return None;
+    }
+    let signature = hex_to_u8(signature).ok()?;
+
+    let mac = Hmac::<Sha1>::mac(key, file?.as_bytes());
+    Some(insecure_compare(&mac, &signature, delay))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::u8_to_hex;
+    use std::io::Read;
+
+    fn get(addr: SocketAddr, path: &str) -> Result<String> {
+        let mut stream = TcpStream::connect(addr)?;
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path)?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response.lines().next().unwrap_or("").to_owned())
+    }
+
+    #[test]
+    fn insecure_compare_matches() {
+        let delay = Duration::ZERO;
+        assert!(insecure_compare(b"abc", b"abc", delay));
+        assert!(!insecure_compare(b"abc", b"abd", delay));
+        assert!(!insecure_compare(b"abc", b"ab", delay));
+    }
+
+    #[test]
+    fn server_checks_signature() -> Result<()> {
+        let key = b"YELLOW SUBMARINE".to_vec();
+        let addr = spawn(hmac_handler(key.clone(), Duration::ZERO))?;
+        let mac = u8_to_hex(&Hmac::<Sha1>::mac(&key, b"foo"));
+
+        let ok = get(addr, &format!("/test?file=foo&signature={}", mac))?;
+        assert_eq!(ok, "HTTP/1.1 200 OK");
+        let bad = get(addr, &format!("/test?file=bar&signature={}", mac))?;
+        assert_eq!(bad, "HTTP/1.1 500 Internal Server Error");
+        let malformed = get(addr, "/test?file=foo&signature=abc")?;
+        assert_eq!(malformed, "HTTP/1.1 400 Bad Request");
+        Ok(())
+    }
+}
@@ -0,0 +1,333 @@
+use crate::bignum::BigUint;
+use crate::dh::Group;
+use crate::hash::hmac::Hmac;
+use crate::{hex_to_u8, u8_to_hex, Result};
+use openssl::sha::{sha256, Sha256};
+use rand::Rng;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+// SRP-6a as in RFC 5054, with SHA-256 as H and the proof being HMAC-SHA256(K, salt) rather than
+// the RFC's M1, which is what the cryptopals challenges use
+// https://www.rfc-editor.org/rfc/rfc5054
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Params {
+    pub group: Group,
+    // The multiplier, H(N | PAD(g))
+    pub k: BigUint,
+}
+
+impl Params {
+    pub fn new(group: Group) -> Self {
+        let k = BigUint::from_bytes_be(&sha256(&pad_pair(&group.p, &group.p, &group.g)));
+        Params { group, k }
+    }
+
+    // u = H(PAD(A) | PAD(B)), which ties the session to both public keys
+    fn scrambler(&self, client_public: &BigUint, server_public: &BigUint) -> BigUint {
+        let n = &self.group.p;
+        BigUint::from_bytes_be(&sha256(&pad_pair(n, client_public, server_public)))
+    }
+
+    // A private key from [1, N - 1)
+    fn private_key(&self) -> BigUint {
+        let bound = &self.group.p - BigUint::from(2);
+        BigUint::random_below(&mut rand::thread_rng(), &bound) + BigUint::one()
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self::new(Group::modp_1536())
+    }
+}
+
+// Both values left padded to the length of N and concatenated
+fn pad_pair(n: &BigUint, a: &BigUint, b: &BigUint) -> Vec<u8> {
+    let len = n.to_bytes_be().len();
+    let mut out = vec![0; 2 * len];
+    let (a, b) = (a.to_bytes_be(), b.to_bytes_be());
+    out[len - a.len()..len].copy_from_slice(&a);
+    out[2 * len - b.len()..].copy_from_slice(&b);
+    out
+}
+
+// x = H(salt | H(I ":" P))
+fn private_value(salt: &[u8], email: &str, password: &str) -> BigUint {
+    let mut h = Sha256::new();
+    h.update(salt);
+    h.update(&sha256(format!("{}:{}", email, password).as_bytes()));
+    BigUint::from_bytes_be(&h.finish())
+}
+
+// K = H(S)
+pub fn session_key(secret: &BigUint) -> [u8; 32] {
+    sha256(&secret.to_bytes_be())
+}
+
+// What the client sends to prove it holds K
+pub fn proof(key: &[u8], salt: &[u8]) -> Vec<u8> {
+    Hmac::<Sha256>::mac(key, salt)
+}
+
+// What the server stores for a user in place of their password
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verifier {
+    pub salt: Vec<u8>,
+    pub v: BigUint,
+}
+
+impl Verifier {
+    // Registration: picks a fresh salt and computes v = g^x mod N
+    pub fn new(params: &Params, email: &str, password: &str) -> Self {
+        let salt: [u8; 16] = rand::thread_rng().gen();
+        let x = private_value(&salt, email, password);
+        Verifier {
+            salt: salt.to_vec(),
+            v: params.group.g.modpow(&x, &params.group.p),
+        }
+    }
+}
+
+// The client's half of a login. `public` is A = g^a mod N.
+pub struct Client {
+    private: BigUint,
+    pub public: BigUint,
+}
+
+impl Client {
+    pub fn new(params: &Params) -> Self {
+        let private = params.private_key();
+        let public = params.group.g.modpow(&private, &params.group.p);
+        Client { private, public }
+    }
+
+    // S = (B - k * g^x)^(a + u * x) mod N, hashed down to K. Errors on the server public keys the
+    // RFC says to refuse.
+    pub fn session_key(
+        &self,
+        params: &Params,
+        email: &str,
+        password: &str,
+        salt: &[u8],
+        server_public: &BigUint,
+    ) -> Result<[u8; 32]> {
+        let n = &params.group.p;
+        let server_public = server_public % n;
+        let u = params.scrambler(&self.public, &server_public);
+        if server_public.is_zero() || u.is_zero() {
+            return Err("error: server sent a bad public key".into());
+        }
+
+        let x = private_value(salt, email, password);
+        let kgx = &params.k * params.group.g.modpow(&x, n) % n;
+        // Adding N first keeps the subtraction from going negative
+        let base = (server_public + n - kgx) % n;
+        let secret = base.modpow(&(&self.private + u * x), n);
+        Ok(session_key(&secret))
+    }
+}
+
+// The server's half of a login for one registered user. `public` is B = k * v + g^b mod N.
+pub struct Server {
+    private: BigUint,
+    verifier: Verifier,
+    pub public: BigUint,
+}
+
+impl Server {
+    pub fn new(params: &Params, verifier: Verifier) -> Self {
+        let n = &params.group.p;
+        let private = params.private_key();
+        let public = (&params.k * &verifier.v + params.group.g.modpow(&private, n)) % n;
+        Server {
+            private,
+            verifier,
+            public,
+        }
+    }
+
+    // S = (A * v^u)^b mod N, hashed down to K. The RFC says to abort when A is 0 mod N, and this
+    // deliberately doesn't: see `attacks::srp`.
+    pub fn session_key(&self, params: &Params, client_public: &BigUint) -> Result<[u8; 32]> {
+        let n = &params.group.p;
+        // Reduced first, so a key too big to pad doesn't get in the way
+        let client_public = client_public % n;
+        let u = params.scrambler(&client_public, &self.public);
+        if u.is_zero() {
+            return Err("error: scrambler came out as zero".into());
+        }
+
+        let secret = (&client_public * self.verifier.v.modpow(&u, n) % n).modpow(&self.private, n);
+        Ok(session_key(&secret))
+    }
+
+    pub fn verify(&self, params: &Params, client_public: &BigUint, tag: &[u8]) -> Result<bool> {
+        let key = self.session_key(params, client_public)?;
+        Ok(Hmac::<Sha256>::verify(&key, &self.verifier.salt, tag))
+    }
+}
+
+// Handles SRP logins for `server::serve`, one line per message:
+//   C: HELLO <email> <A>
+//   S: <salt> <B>        (or DENIED for unknown users and bad requests)
+//   C: PROOF <hmac>
+//   S: OK or DENIED
+// Everything after the command is hex.
+pub fn login_handler(
+    params: Params,
+    users: HashMap<String, Verifier>,
+) -> impl Fn(TcpStream) -> Result<()> + Send + Sync + 'static {
+    move |stream| handle(stream, &params, &users)
+}
+
+fn handle(mut stream: TcpStream, params: &Params, users: &HashMap<String, Verifier>) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let hello = match line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["HELLO", email, public] => users
+            .get(email)
+            .zip(BigUint::from_hex(public).ok())
+            .map(|(verifier, public)| (Server::new(params, verifier.clone()), public)),
+        _ => None,
+    };
+    let Some((server, client_public)) = hello else {
+        stream.write_all(b"DENIED\n")?;
+        return Ok(());
+    };
+    let reply = format!(
+        "{} {}\n",
+        u8_to_hex(&server.verifier.salt),
+        server.public.to_hex()
+    );
+    stream.write_all(reply.as_bytes())?;
+
+    line.clear();
+    reader.read_line(&mut line)?;
+    let accepted = match line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["PROOF", tag] if tag.is_ascii() && tag.len().is_multiple_of(2) => {
+            let tag = hex_to_u8(tag)?;
+            server.verify(params, &client_public, &tag)?
+        }
+        _ => false,
+    };
+    stream.write_all(if accepted { b"OK\n" } else { b"DENIED\n" })?;
+    Ok(())
+}
+
+// A login in progress over the network: the server has answered HELLO with its salt and B and is
+// waiting on a proof
+pub struct Handshake {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+    pub salt: Vec<u8>,
+    pub server_public: BigUint,
+}
+
+impl Handshake {
+    // Sends `client_public` as A, which is normally g^a mod N but needn't be
+    pub fn start(addr: impl ToSocketAddrs, email: &str, client_public: &BigUint) -> Result<Self> {
+        let mut stream = TcpStream::connect(addr)?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        stream.write_all(format!("HELLO {} {}\n", email, client_public.to_hex()).as_bytes())?;
+
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let Some((salt, public)) = line.trim_end().split_once(' ') else {
+            return Err("error: login refused".into());
+        };
+        if !salt.is_ascii() || !salt.len().is_multiple_of(2) {
+            return Err("error: bad salt from server".into());
+        }
+        Ok(Handshake {
+            salt: hex_to_u8(salt)?,
+            server_public: BigUint::from_hex(public)?,
+            stream,
+            reader,
+        })
+    }
+
+    // Sends the proof for `key` and returns whether the server let us in
+    pub fn finish(mut self, key: &[u8]) -> Result<bool> {
+        let tag = u8_to_hex(&proof(key, &self.salt));
+        self.stream
+            .write_all(format!("PROOF {}\n", tag).as_bytes())?;
+        let mut line = String::new();
+        self.reader.read_line(&mut line)?;
+        Ok(line.trim_end() == "OK")
+    }
+}
+
+// Logs in the honest way
+pub fn login(
+    addr: impl ToSocketAddrs,
+    params: &Params,
+    email: &str,
+    password: &str,
+) -> Result<bool> {
+    let client = Client::new(params);
+    let handshake = Handshake::start(addr, email, &client.public)?;
+    let key = client.session_key(
+        params,
+        email,
+        password,
+        &handshake.salt,
+        &handshake.server_public,
+    )?;
+    handshake.finish(&key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server;
+
+    const EMAIL: &str = "alice@example.com";
+    const PASSWORD: &str = "correct horse battery staple";
+
+    fn users(params: &Params) -> HashMap<String, Verifier> {
+        HashMap::from([(EMAIL.to_owned(), Verifier::new(params, EMAIL, PASSWORD))])
+    }
+
+    #[test]
+    fn keys_agree() -> Result<()> {
+        let params = Params::default();
+        let verifier = Verifier::new(&params, EMAIL, PASSWORD);
+        let salt = verifier.salt.clone();
+        let client = Client::new(&params);
+        let server = Server::new(&params, verifier);
+
+        let client_key = client.session_key(&params, EMAIL, PASSWORD, &salt, &server.public)?;
+        assert_eq!(client_key, server.session_key(&params, &client.public)?);
+        assert!(server.verify(&params, &client.public, &proof(&client_key, &salt))?);
+
+        let wrong = client.session_key(&params, EMAIL, "hunter2", &salt, &server.public)?;
+        assert_ne!(wrong, client_key);
+        assert!(!server.verify(&params, &client.public, &proof(&wrong, &salt))?);
+        Ok(())
+    }
+
+    #[test]
+    fn login_over_tcp() -> Result<()> {
+        let params = Params::default();
+        let addr = server::spawn(login_handler(params.clone(), users(&params)))?;
+
+        assert!(login(addr, &params, EMAIL, PASSWORD)?);
+        assert!(!login(addr, &params, EMAIL, "hunter2")?);
+        assert!(login(addr, &params, "mallory@example.com", PASSWORD).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn client_refuses_zero_server_key() {
+        let params = Params::default();
+        let client = Client::new(&params);
+        let n = params.group.p.clone();
+        assert!(client
+            .session_key(&params, EMAIL, PASSWORD, b"salt", &n)
+            .is_err());
+    }
+}